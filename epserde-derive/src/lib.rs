@@ -12,11 +12,11 @@ Derive procedural macros for the [`epserde`](https://crates.io/crates/epserde) c
 */
 
 use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{parse_macro_input, Data, DeriveInput};
 use syn::{
-    punctuated::Punctuated, token, BoundLifetimes, GenericParam, LifetimeParam, PredicateType,
-    WhereClause, WherePredicate,
+    punctuated::Punctuated, spanned::Spanned, token, BoundLifetimes, GenericParam, LifetimeParam,
+    PredicateType, WhereClause, WherePredicate,
 };
 
 /// Pre-parsed information for the derive macros.
@@ -109,35 +109,880 @@ impl CommonDeriveInput {
     }
 }
 
+/// The primitive representations of enums.
+const INT_REPRS: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Return the list of representations specified by `repr` attributes
+/// (e.g., `["C", "align (16)"]` for `#[repr(C, align(16))]`).
+fn reprs(attrs: &[syn::Attribute]) -> syn::Result<Vec<String>> {
+    let mut reprs = vec![];
+    for attr in attrs.iter().filter(|x| x.meta.path().is_ident("repr")) {
+        let metas =
+            attr.parse_args_with(Punctuated::<syn::Meta, token::Comma>::parse_terminated)?;
+        reprs.extend(
+            metas
+                .into_iter()
+                .map(|meta| meta.to_token_stream().to_string()),
+        );
+    }
+    Ok(reprs)
+}
+
+/// Return the primitive representation of an enum, if any.
+fn int_repr(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Ident>> {
+    Ok(reprs(attrs)?
+        .into_iter()
+        .find(|repr| INT_REPRS.contains(&repr.as_str()))
+        .map(|repr| syn::Ident::new(&repr, proc_macro2::Span::call_site())))
+}
+
+/// Return the string representations of the `repr` attributes, which are
+/// hashed in type hashes.
+fn repr_strings(attrs: &[syn::Attribute]) -> syn::Result<Vec<String>> {
+    attrs
+        .iter()
+        .filter(|x| x.meta.path().is_ident("repr"))
+        .map(|x| Ok(x.meta.require_list()?.tokens.to_string()))
+        .collect()
+}
+
+/// Return the tags of the variants of a deep-copy enum, and the type used to
+/// serialize them.
+///
+/// The tag of a variant is given by the attribute `#[epserde(tag_value = N)]`,
+/// or by its explicit discriminant, which must be an integer literal;
+/// otherwise, as it happens for discriminants, it is the tag of the previous
+/// variant plus one (or zero for the first variant). The type of tags is given
+/// by the attribute `#[epserde(tag = "u8")]` on the enum; otherwise, it is the
+/// smallest unsigned integer type that can represent all tags.
+fn enum_tags(attrs: &[syn::Attribute], e: &syn::DataEnum) -> syn::Result<(Vec<u64>, syn::Ident)> {
+    let mut tags: Vec<u64> = vec![];
+    for variant in &e.variants {
+        let mut tag_value = None;
+        for attr in variant
+            .attrs
+            .iter()
+            .filter(|x| x.meta.path().is_ident("epserde"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag_value") {
+                    tag_value = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported epserde attribute"))
+                }
+            })?;
+        }
+        let discriminant = match &variant.discriminant {
+            Some((
+                _,
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(lit),
+                    ..
+                }),
+            )) => Some(lit.base10_parse::<u64>()?),
+            Some((_, expr)) => {
+                return Err(syn::Error::new_spanned(
+                    expr,
+                    "the discriminant of a deep-copy enum must be a nonnegative integer literal; use #[epserde(tag_value = N)] instead",
+                ))
+            }
+            None => None,
+        };
+        let tag = match (tag_value, discriminant) {
+            (Some(tag_value), Some(discriminant)) if tag_value != discriminant => {
+                return Err(syn::Error::new_spanned(
+                    &variant.ident,
+                    "the tag value differs from the discriminant",
+                ))
+            }
+            (Some(tag), _) | (None, Some(tag)) => tag,
+            (None, None) => tags.last().map_or(0, |last| last + 1),
+        };
+        if tags.contains(&tag) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!("the tag {} is already used by another variant", tag),
+            ));
+        }
+        tags.push(tag);
+    }
+
+    let max_tag = tags.iter().copied().max().unwrap_or(0);
+    let mut tag_type = None;
+    for attr in attrs.iter().filter(|x| x.meta.path().is_ident("epserde")) {
+        // Other attributes are checked by check_attrs
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("tag") {
+                tag_type = Some(meta.value()?.parse::<syn::LitStr>()?);
+            }
+            Ok(())
+        });
+    }
+    let tag_type = match tag_type {
+        Some(tag_type) => {
+            let max = match tag_type.value().as_str() {
+                "u8" => u8::MAX as u64,
+                "u16" => u16::MAX as u64,
+                "u32" => u32::MAX as u64,
+                "u64" => u64::MAX,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &tag_type,
+                        "the tag type must be one of u8, u16, u32, or u64",
+                    ))
+                }
+            };
+            if max_tag > max {
+                return Err(syn::Error::new_spanned(
+                    &tag_type,
+                    format!(
+                        "the tag {} does not fit into a {}",
+                        max_tag,
+                        tag_type.value()
+                    ),
+                ));
+            }
+            tag_type.value()
+        }
+        None => match max_tag {
+            0..=0xFF => "u8",
+            0x100..=0xFFFF => "u16",
+            0x10000..=0xFFFF_FFFF => "u32",
+            _ => "u64",
+        }
+        .to_string(),
+    };
+
+    Ok((
+        tags,
+        syn::Ident::new(&tag_type, proc_macro2::Span::call_site()),
+    ))
+}
+
+/// Field attributes.
+#[derive(Default)]
+struct FieldAttrs {
+    /// The field is not serialized (`#[epserde(skip)]`).
+    skip: bool,
+    /// The function building the value of a skipped field
+    /// (`#[epserde(default = "path")]`); if missing, we use `Default::default`.
+    default: Option<syn::Path>,
+    /// The module providing custom serialization and deserialization
+    /// functions (`#[epserde(with = "module")]`).
+    with: Option<syn::Path>,
+    /// The name of the field in type hashes (`#[epserde(rename = "name")]`).
+    rename: Option<String>,
+    /// The minimum alignment of the zero-copy data of the field
+    /// (`#[epserde(align = N)]`).
+    align: Option<usize>,
+}
+
+impl FieldAttrs {
+    /// Parse the `epserde` attributes of a field.
+    fn new(field: &syn::Field) -> syn::Result<Self> {
+        let mut attrs = FieldAttrs::default();
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|x| x.meta.path().is_ident("epserde"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    attrs.skip = true;
+                } else if meta.path.is_ident("default") {
+                    attrs.default = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+                } else if meta.path.is_ident("with") {
+                    attrs.with = Some(meta.value()?.parse::<syn::LitStr>()?.parse()?);
+                } else if meta.path.is_ident("rename") {
+                    attrs.rename = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("align") {
+                    let lit = meta.value()?.parse::<syn::LitInt>()?;
+                    let align = lit.base10_parse::<usize>()?;
+                    if !align.is_power_of_two() {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "the alignment must be a power of two",
+                        ));
+                    }
+                    attrs.align = Some(align);
+                } else {
+                    return Err(meta.error("unsupported epserde attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        if attrs.default.is_some() && !attrs.skip {
+            return Err(syn::Error::new_spanned(
+                field,
+                "a default can be specified only for skipped fields",
+            ));
+        }
+        if attrs.skip && attrs.with.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "the field is both skipped and serialized with a custom module",
+            ));
+        }
+        if attrs.is_custom() && attrs.align.is_some() {
+            return Err(syn::Error::new_spanned(
+                field,
+                "an alignment can be specified only for fields serialized by ε-serde",
+            ));
+        }
+        Ok(attrs)
+    }
+
+    /// Return whether the field is not serialized by ε-serde.
+    fn is_custom(&self) -> bool {
+        self.skip || self.with.is_some()
+    }
+}
+
+/// Return the name of a field, or its index for tuple structures.
+fn field_name(field_idx: usize, field: &syn::Field) -> String {
+    field
+        .ident
+        .as_ref()
+        .map(|ident| ident.to_string())
+        .unwrap_or_else(|| field_idx.to_string())
+}
+
+/// Return an error if the fields of a variant have `epserde` attributes, which are
+/// supported only on structures.
+fn check_no_field_attrs(fields: &syn::Fields) -> syn::Result<()> {
+    match fields
+        .iter()
+        .flat_map(|field| field.attrs.iter())
+        .find(|x| x.meta.path().is_ident("epserde"))
+    {
+        Some(attr) => Err(syn::Error::new_spanned(
+            attr,
+            "epserde field attributes are supported only on structures",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Return the path of the attribute `#[epserde(deser_struct)]` of a structure, if any.
+fn deser_struct_path(attrs: &[syn::Attribute]) -> Option<syn::Path> {
+    epserde_flag_path(attrs, "deser_struct")
+}
+
+/// Return the path of the attribute `#[epserde(nested_params)]` of a type, if any.
+fn nested_params_path(attrs: &[syn::Attribute]) -> Option<syn::Path> {
+    epserde_flag_path(attrs, "nested_params")
+}
+
+/// Return the path of the flag `#[epserde(name)]` of a type, if any.
+fn epserde_flag_path(attrs: &[syn::Attribute], name: &str) -> Option<syn::Path> {
+    let mut path = None;
+    attrs
+        .iter()
+        .filter(|x| x.meta.path().is_ident("epserde"))
+        .for_each(|x| {
+            // Other attributes are checked by check_attrs
+            let _ = x.parse_nested_meta(|meta| {
+                if meta.path.is_ident(name) {
+                    path = Some(meta.path.clone());
+                }
+                Ok(())
+            });
+        });
+    path
+}
+
 /// Return whether the struct has attributes `repr(C)`, `zero_copy`, and `deep_copy`.
 ///
+/// For structures, `repr(transparent)` is equivalent to `repr(C)`, and for
+/// enums a primitive representation (e.g., `repr(u8)`) is equivalent to
+/// `repr(C)`, as in all cases the layout is defined. Alignment modifiers such
+/// as `repr(C, align(16))` are allowed.
+///
 /// Performs coherence checks (e.g., to be `zero_copy` the struct must be `repr(C)`).
-fn check_attrs(input: &DeriveInput) -> (bool, bool, bool) {
-    let is_repr_c = input.attrs.iter().any(|x| {
-        x.meta.path().is_ident("repr") && x.meta.require_list().unwrap().tokens.to_string() == "C"
-    });
-    let is_zero_copy = input
+fn check_attrs(input: &DeriveInput) -> syn::Result<(bool, bool, bool, bool)> {
+    let reprs = reprs(&input.attrs)?;
+    let is_repr_c = reprs.iter().any(|x| x == "C")
+        || (matches!(input.data, Data::Struct(_)) && reprs.iter().any(|x| x == "transparent"))
+        || (matches!(input.data, Data::Enum(_)) && int_repr(&input.attrs)?.is_some());
+    let zero_copy = input
         .attrs
         .iter()
-        .any(|x| x.meta.path().is_ident("zero_copy"));
-    let is_deep_copy = input
+        .find(|x| x.meta.path().is_ident("zero_copy"));
+    let deep_copy = input
         .attrs
         .iter()
-        .any(|x| x.meta.path().is_ident("deep_copy"));
-    if is_zero_copy && !is_repr_c {
-        panic!(
-            "Type {} is declared as zero copy, but it is not repr(C)",
-            input.ident
-        );
+        .find(|x| x.meta.path().is_ident("deep_copy"));
+    let is_zero_copy = zero_copy.is_some();
+    let is_deep_copy = deep_copy.is_some();
+    if let Some(zero_copy) = zero_copy {
+        if !is_repr_c {
+            return Err(syn::Error::new_spanned(
+                zero_copy,
+                format!(
+                    "type {} is declared as zero copy, but it is not repr(C)",
+                    input.ident
+                ),
+            ));
+        }
+        if let Some(deep_copy) = deep_copy {
+            return Err(syn::Error::new_spanned(
+                deep_copy,
+                format!(
+                    "type {} is declared as both zero copy and deep copy",
+                    input.ident
+                ),
+            ));
+        }
+    }
+    let mut is_zero_copy_option = None;
+    let mut nested_params = None;
+    let mut tag = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|x| x.meta.path().is_ident("epserde"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("zero_copy_option") {
+                is_zero_copy_option = Some(meta.path.clone());
+                Ok(())
+            } else if meta.path.is_ident("deser_struct") && matches!(input.data, Data::Struct(_)) {
+                // Parsed by deser_struct_path
+                Ok(())
+            } else if meta.path.is_ident("nested_params") {
+                // Parsed by nested_params_path
+                nested_params = Some(meta.path.clone());
+                Ok(())
+            } else if meta.path.is_ident("tag") && matches!(input.data, Data::Enum(_)) {
+                // Parsed by enum_tags
+                tag = Some(meta.path.clone());
+                meta.value()?.parse::<syn::LitStr>()?;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported epserde attribute"))
+            }
+        })?;
+    }
+    if let Some(tag) = tag.filter(|_| is_zero_copy) {
+        return Err(syn::Error::new_spanned(
+            tag,
+            format!(
+                "type {} declares a tag type, but it is zero copy: use a primitive representation instead",
+                input.ident
+            ),
+        ));
+    }
+    if let Some(nested_params) = nested_params.filter(|_| is_zero_copy) {
+        return Err(syn::Error::new_spanned(
+            nested_params,
+            format!(
+                "type {} declares nested parameters, but it is zero copy",
+                input.ident
+            ),
+        ));
+    }
+    if let Some(zero_copy_option) = is_zero_copy_option.as_ref().filter(|_| !is_zero_copy) {
+        return Err(syn::Error::new_spanned(
+            zero_copy_option,
+            format!(
+                "type {} declares zero-copy options, but it is not zero copy",
+                input.ident
+            ),
+        ));
     }
-    if is_zero_copy && is_deep_copy {
-        panic!(
-            "Type {} is declared as both zero copy and deep copy",
-            input.ident
+
+    Ok((
+        is_repr_c,
+        is_zero_copy,
+        is_deep_copy,
+        is_zero_copy_option.is_some(),
+    ))
+}
+
+/// Return whether a type mentions one of the given lifetimes.
+///
+/// Fields whose type mentions a lifetime parameter of the structure are
+/// ε-copy deserialized, and their deserialization type is assumed to be the
+/// same type with the lifetime parameter replaced by the deserialization
+/// lifetime, as it happens for `Cow<'a, [T]>` and `Cow<'a, str>`.
+fn mentions_lifetimes(ty: &syn::Type, lifetimes: &[String]) -> bool {
+    fn visit(tokens: proc_macro2::TokenStream, lifetimes: &[String]) -> bool {
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token {
+                proc_macro2::TokenTree::Group(group) if visit(group.stream(), lifetimes) => {
+                    return true;
+                }
+                proc_macro2::TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                    if let Some(proc_macro2::TokenTree::Ident(ident)) = tokens.peek() {
+                        if lifetimes.contains(&format!("'{}", ident)) {
+                            return true;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        false
+    }
+    visit(ty.to_token_stream(), lifetimes)
+}
+
+/// Return a string representation of a token stream in which paths are
+/// replaced by their last segment (e.g., `std::net::Ipv4Addr` becomes
+/// `Ipv4Addr`), so that it does not depend on how paths are written.
+fn unqualified(tokens: proc_macro2::TokenStream) -> String {
+    fn visit(tokens: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+        let tokens = tokens.into_iter().collect::<Vec<_>>();
+        let is_path_sep = |i: usize| {
+            matches!((tokens.get(i), tokens.get(i + 1)), (
+                Some(proc_macro2::TokenTree::Punct(first)),
+                Some(proc_macro2::TokenTree::Punct(second)),
+            ) if first.as_char() == ':'
+                && first.spacing() == proc_macro2::Spacing::Joint
+                && second.as_char() == ':')
+        };
+        let mut result = proc_macro2::TokenStream::new();
+        let mut i = 0;
+        while i < tokens.len() {
+            match &tokens[i] {
+                // Skip a path segment and the following separator
+                proc_macro2::TokenTree::Ident(_) if is_path_sep(i + 1) => i += 3,
+                // Skip any other separator (e.g., a leading one)
+                proc_macro2::TokenTree::Punct(_) if is_path_sep(i) => i += 2,
+                proc_macro2::TokenTree::Group(group) => {
+                    result.extend([proc_macro2::TokenTree::from(proc_macro2::Group::new(
+                        group.delimiter(),
+                        visit(group.stream()),
+                    ))]);
+                    i += 1;
+                }
+                token => {
+                    result.extend([token.clone()]);
+                    i += 1;
+                }
+            }
+        }
+        result
+    }
+    visit(tokens).to_string()
+}
+
+/// Replace the given lifetimes in a token stream with the deserialization lifetime.
+fn replace_lifetimes(
+    tokens: proc_macro2::TokenStream,
+    lifetimes: &[String],
+) -> proc_macro2::TokenStream {
+    let mut out = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            proc_macro2::TokenTree::Group(group) => {
+                let mut new_group = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_lifetimes(group.stream(), lifetimes),
+                );
+                new_group.set_span(group.span());
+                out.push(new_group.into());
+            }
+            proc_macro2::TokenTree::Punct(ref punct) if punct.as_char() == '\'' => {
+                match tokens.peek() {
+                    Some(proc_macro2::TokenTree::Ident(ident))
+                        if lifetimes.contains(&format!("'{}", ident)) =>
+                    {
+                        tokens.next();
+                        out.extend(quote!('epserde_desertype));
+                    }
+                    _ => out.push(token),
+                }
+            }
+            _ => out.push(token),
+        }
+    }
+    out.into_iter().collect()
+}
+
+/// Return whether a type mentions the given type parameter.
+fn mentions_type_param(ty: &impl ToTokens, param: &str) -> bool {
+    fn visit(tokens: proc_macro2::TokenStream, param: &str) -> bool {
+        tokens.into_iter().any(|token| match token {
+            proc_macro2::TokenTree::Group(group) => visit(group.stream(), param),
+            proc_macro2::TokenTree::Ident(ident) => ident == param,
+            _ => false,
+        })
+    }
+    visit(ty.to_token_stream(), param)
+}
+
+/// Return whether a type is built from the given type parameters using only
+/// vectors, boxed slices, arrays, tuples, and options.
+///
+/// If the type parameters are deep-copy, the deserialization type of such a
+/// type is the type itself with the type parameters replaced by their
+/// deserialization types: for example, the deserialization type of `Vec<T>`
+/// is `Vec<<T as DeserializeInner>::DeserType<'a>>`.
+fn is_structural(ty: &syn::Type, params: &[String]) -> bool {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            if let Some(ident) = path.path.get_ident() {
+                return params.contains(&ident.to_string());
+            }
+            let last = path.path.segments.last().unwrap();
+            let syn::PathArguments::AngleBracketed(args) = &last.arguments else {
+                return false;
+            };
+            let args = args.args.iter().collect::<Vec<_>>();
+            match (last.ident.to_string().as_str(), args.as_slice()) {
+                ("Vec" | "Option", [syn::GenericArgument::Type(ty)]) => is_structural(ty, params),
+                ("Box", [syn::GenericArgument::Type(syn::Type::Slice(slice))]) => {
+                    is_structural(&slice.elem, params)
+                }
+                _ => false,
+            }
+        }
+        syn::Type::Array(array) => is_structural(&array.elem, params),
+        syn::Type::Tuple(tuple) => {
+            !tuple.elems.is_empty() && tuple.elems.iter().all(|elem| is_structural(elem, params))
+        }
+        syn::Type::Paren(paren) => is_structural(&paren.elem, params),
+        syn::Type::Group(group) => is_structural(&group.elem, params),
+        _ => false,
+    }
+}
+
+/// Return whether a type parameter is declared `DeepCopy` or `ZeroCopy`,
+/// either inline or in the where clause.
+fn is_classified_param(generics: &syn::Generics, param: &str) -> bool {
+    let is_copy_type = |bound: &syn::TypeParamBound| {
+        matches!(bound, syn::TypeParamBound::Trait(t)
+            if t.path.segments.last().is_some_and(|s| s.ident == "DeepCopy" || s.ident == "ZeroCopy"))
+    };
+    generics
+        .type_params()
+        .any(|t| t.ident == param && t.bounds.iter().any(is_copy_type))
+        || generics.where_clause.iter().any(|where_clause| {
+            where_clause.predicates.iter().any(|predicate| {
+                matches!(predicate, WherePredicate::Type(p)
+                    if matches!(&p.bounded_ty, syn::Type::Path(path)
+                        if path.qself.is_none() && path.path.is_ident(param))
+                    && p.bounds.iter().any(is_copy_type))
+            })
+        })
+}
+
+/// Return the type parameters that are replaced by their deserialization
+/// types in the deserialization type, in order of declaration.
+///
+/// A type parameter is replaced if it is the type of a field, or if `nested`
+/// is true, it is not declared `DeepCopy` or `ZeroCopy`, and all fields
+/// mentioning it are [structural](is_structural) (fields that are skipped or
+/// serialized with a custom module, whose types are given separately, are never
+/// structural). Fields with structural types mentioning replaced type
+/// parameters are ε-copy deserialized.
+fn substituted_type_params(
+    generics: &syn::Generics,
+    fields_types: &[&syn::Type],
+    custom_types: &[&syn::Type],
+    nested: bool,
+) -> Vec<String> {
+    let type_params = generics
+        .type_params()
+        .map(|t| t.ident.to_string())
+        .collect::<Vec<_>>();
+    let is_field_type = |param: &String| {
+        fields_types
+            .iter()
+            .any(|ty| ty.to_token_stream().to_string() == *param)
+    };
+    let mut substituted = type_params
+        .iter()
+        .filter(|param| is_field_type(param) || (nested && !is_classified_param(generics, param)))
+        .cloned()
+        .collect::<Vec<_>>();
+    // Removing a type parameter might make other fields non structural,
+    // so we iterate until we reach a fixed point.
+    loop {
+        let blocked = substituted
+            .iter()
+            .filter(|param| {
+                !is_field_type(param)
+                    && (fields_types.iter().any(|ty| {
+                        mentions_type_param(ty, param) && !is_structural(ty, &substituted)
+                    }) || custom_types.iter().any(|ty| mentions_type_param(ty, param)))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        if blocked.is_empty() {
+            return substituted;
+        }
+        substituted.retain(|param| !blocked.contains(param));
+    }
+}
+
+/// Return whether a field type is not a type parameter, but it is
+/// [structural](is_structural) in the given type parameters, and thus it
+/// must be ε-copy deserialized.
+fn is_nested_generic(ty: &syn::Type, params: &[String]) -> bool {
+    !params.contains(&ty.to_token_stream().to_string()) && is_structural(ty, params)
+}
+
+/// Replace the given type parameters in a token stream with their (possibly
+/// mutable) deserialization types.
+fn replace_type_params(
+    tokens: proc_macro2::TokenStream,
+    params: &[String],
+    mutable: bool,
+) -> proc_macro2::TokenStream {
+    tokens
+        .into_iter()
+        .flat_map(|token| -> proc_macro2::TokenStream {
+            match token {
+                proc_macro2::TokenTree::Group(group) => {
+                    let mut new_group = proc_macro2::Group::new(
+                        group.delimiter(),
+                        replace_type_params(group.stream(), params, mutable),
+                    );
+                    new_group.set_span(group.span());
+                    proc_macro2::TokenTree::from(new_group).into()
+                }
+                proc_macro2::TokenTree::Ident(ident) if params.contains(&ident.to_string()) => {
+                    if mutable {
+                        quote!(<#ident as epserde::deser::DeserializeMutInner>::DeserMutType<'epserde_desertype>)
+                    } else {
+                        quote!(<#ident as epserde::deser::DeserializeInner>::DeserType<'epserde_desertype>)
+                    }
+                }
+                token => token.into(),
+            }
+        })
+        .collect()
+}
+
+/// Return the bound requiring that a field type implements `DeserializeInner`.
+///
+/// If the type mentions some of the given lifetimes, or it is
+/// [nested generic](is_nested_generic) in the given type parameters, we also
+/// require that its deserialization type is the type itself with the lifetimes
+/// replaced by the deserialization lifetime and the type parameters replaced
+/// by their deserialization types, as otherwise the bound would hide the
+/// associated type of the implementation.
+fn deser_bound(ty: &syn::Type, lifetimes: &[String], params: &[String]) -> WherePredicate {
+    if mentions_lifetimes(ty, lifetimes) || is_nested_generic(ty, params) {
+        let deser_type = replace_type_params(
+            replace_lifetimes(ty.to_token_stream(), lifetimes),
+            params,
+            false,
         );
+        syn::parse_quote!(
+            for<'epserde_desertype> #ty: epserde::deser::DeserializeInner<DeserType<'epserde_desertype> = #deser_type>
+        )
+    } else {
+        syn::parse_quote!(#ty: epserde::deser::DeserializeInner)
     }
+}
 
-    (is_repr_c, is_zero_copy, is_deep_copy)
+/// Return the bound requiring that the mutable deserialization type of a
+/// [nested generic](is_nested_generic) field type is the type itself with the
+/// type parameters replaced by their mutable deserialization types.
+fn deser_mut_bound(ty: &syn::Type, params: &[String]) -> WherePredicate {
+    let deser_mut_type = replace_type_params(ty.to_token_stream(), params, true);
+    syn::parse_quote!(
+        for<'epserde_desertype> #ty: epserde::deser::DeserializeMutInner<DeserMutType<'epserde_desertype> = #deser_mut_type>
+    )
+}
+
+/// Return the bounds on the generic types involved in deserialization type
+/// substitution, whether inlined or in the where clause, as predicates.
+fn generic_bounds(generics: &syn::Generics, generic_types: &[String]) -> Vec<PredicateType> {
+    let mut result = vec![];
+    generics.params.iter().for_each(|param| {
+        if let GenericParam::Type(t) = param {
+            if !t.bounds.is_empty() && generic_types.contains(&t.ident.to_string()) {
+                let ty = &t.ident;
+                result.push(PredicateType {
+                    lifetimes: None,
+                    bounded_ty: syn::parse_quote!(#ty),
+                    colon_token: token::Colon::default(),
+                    bounds: t.bounds.clone(),
+                });
+            }
+        }
+    });
+    if let Some(where_clause) = &generics.where_clause {
+        where_clause.predicates.iter().for_each(|predicate| {
+            if let WherePredicate::Type(p) = predicate {
+                if let syn::Type::Path(path) = &p.bounded_ty {
+                    if path.qself.is_none()
+                        && !p.bounds.is_empty()
+                        && path
+                            .path
+                            .get_ident()
+                            .is_some_and(|ident| generic_types.contains(&ident.to_string()))
+                    {
+                        result.push(p.clone());
+                    }
+                }
+            }
+        });
+    }
+    result
+}
+
+/// Turn a predicate on a generic type into the same predicate on its (possibly
+/// mutable) deserialization type.
+fn deser_type_bound(predicate: &PredicateType, mutable: bool) -> WherePredicate {
+    let ty = &predicate.bounded_ty;
+    // add a lifetime so we express bounds on DeserType
+    let mut lifetimes = predicate
+        .lifetimes
+        .as_ref()
+        .map(|l| l.lifetimes.clone())
+        .unwrap_or_default();
+    lifetimes.insert(
+        0,
+        GenericParam::Lifetime(LifetimeParam {
+            attrs: vec![],
+            lifetime: syn::Lifetime::new("'epserde_desertype", proc_macro2::Span::call_site()),
+            colon_token: None,
+            bounds: Punctuated::new(),
+        }),
+    );
+    let bounded_ty = if mutable {
+        syn::parse_quote!(
+            <#ty as epserde::deser::DeserializeMutInner>::DeserMutType<'epserde_desertype>
+        )
+    } else {
+        syn::parse_quote!(
+            <#ty as epserde::deser::DeserializeInner>::DeserType<'epserde_desertype>
+        )
+    };
+    WherePredicate::Type(PredicateType {
+        lifetimes: Some(BoundLifetimes {
+            for_token: token::For::default(),
+            lt_token: token::Lt::default(),
+            lifetimes,
+            gt_token: token::Gt::default(),
+        }),
+        bounded_ty,
+        colon_token: token::Colon::default(),
+        bounds: predicate.bounds.clone(),
+    })
+}
+
+/// Build the where clause for the implementation of `DeserializeMutInner`,
+/// starting from the where clause for `DeserializeInner`.
+///
+/// We add that types of generic fields implement `DeserializeMutInner`,
+/// the bounds on the mutable deserialization types of the fields derived from
+/// the bounds of the original types of the fields, and the mutable
+/// deserialization types of the given [nested generic](is_nested_generic)
+/// fields.
+///
+/// Moreover, we require that the mutable deserialization type of the given
+/// fully deserialized fields is the type itself: otherwise, mutations through
+/// the fully deserialized copy would be silently lost. The bound is
+/// higher-ranked, so that it is not a trivial bound and the implementation
+/// simply does not apply when it is not satisfied.
+fn where_clause_des_mut(
+    where_clause_des: &WhereClause,
+    generics: &syn::Generics,
+    generic_types: &[String],
+    nested_types: &[&syn::Type],
+    full_types: &[&syn::Type],
+) -> WhereClause {
+    let mut where_clause_des_mut = where_clause_des.clone();
+    generics.params.iter().for_each(|param| {
+        if let GenericParam::Type(t) = param {
+            let ty = &t.ident;
+
+            // Skip generics not involved in deserialization type substitution.
+            if generic_types.contains(&ty.to_string()) {
+                where_clause_des_mut
+                    .predicates
+                    .push(syn::parse_quote!(#ty: epserde::deser::DeserializeMutInner));
+            }
+        }
+    });
+    generic_bounds(generics, generic_types)
+        .iter()
+        .for_each(|predicate| {
+            where_clause_des_mut
+                .predicates
+                .push(deser_type_bound(predicate, true));
+        });
+    nested_types.iter().for_each(|ty| {
+        where_clause_des_mut
+            .predicates
+            .push(deser_mut_bound(ty, generic_types));
+    });
+    full_types.iter().for_each(|ty| {
+        where_clause_des_mut.predicates.push(syn::parse_quote!(
+            for<'epserde_desertype> #ty: epserde::deser::DeserializeMutInner<DeserMutType<'epserde_desertype> = #ty>
+        ));
+    });
+    where_clause_des_mut
+}
+
+/// Add to a where clause of an implementation for a zero-copy type the
+/// requirement that the type and the types of its fields are zero-copy.
+///
+/// The bounds are higher-ranked, so that they are not trivial bounds: if a
+/// field is not zero-copy the implementation simply does not apply, rather
+/// than generating an error for each method, and the field is reported
+/// once by [`zero_copy_fields_check`].
+fn add_zero_copy_bounds(where_clause: &mut WhereClause, fields_types: &[impl ToTokens]) {
+    where_clause.predicates.push(syn::parse_quote!(
+        for<'epserde_zero_copy> Self: epserde::traits::ZeroCopy
+    ));
+    fields_types.iter().for_each(|ty| {
+        where_clause.predicates.push(syn::parse_quote!(
+            for<'epserde_zero_copy> #ty: epserde::traits::ZeroCopy
+        ));
+    });
+}
+
+/// Return, for each field of a zero-copy type, a constant whose evaluation
+/// fails, pointing at the field, if the field is not zero-copy.
+///
+/// Fields are described by a string such as ``field `a` ``, which is used
+/// in the error message.
+///
+/// Fields whose type depends on the parameters of the type cannot be checked
+/// at the definition site: they are checked by the bounds added by
+/// [`add_zero_copy_bounds`] when the type is used.
+fn zero_copy_fields_check(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    fields: &[(String, impl ToTokens)],
+) -> proc_macro2::TokenStream {
+    let params = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Type(t) => t.ident.to_string(),
+            GenericParam::Lifetime(l) => l.lifetime.ident.to_string(),
+            GenericParam::Const(c) => c.ident.to_string(),
+        })
+        .collect::<Vec<_>>();
+    let checks = fields
+        .iter()
+        .filter(|(_, ty)| !params.iter().any(|param| mentions_type_param(ty, param)))
+        .map(|(field, ty)| {
+            let message = format!(
+                "{} of zero-copy type `{}` is not zero-copy",
+                field, name
+            );
+            quote_spanned! {ty.span()=>
+                const _: () = assert!(
+                    <<#ty as epserde::traits::CopyType>::Copy as epserde::traits::CopySelector>::IS_ZERO_COPY,
+                    #message
+                );
+            }
+        });
+    quote!(#(#checks)*)
 }
 
 /// Generate an ε-serde implementation for custom types.
@@ -151,17 +996,117 @@ fn check_attrs(input: &DeriveInput) -> (bool, bool, bool) {
 ///
 /// The attribute `zero_copy` can be used to generate an implementation for a zero-copy
 /// type, but the type must be `repr(C)` and all fields must be zero-copy.
+/// Alignment modifiers such as `repr(C, align(16))` are accepted, and so is
+/// `repr(transparent)` for structures, as the layout is defined in both cases.
+///
+/// Enums can be zero-copy, too, if they have a primitive representation (e.g.,
+/// `repr(u8)`), or `repr(C)`, and all fields of all variants are zero-copy.
+/// The discriminant and the fields of the variant are validated when
+/// deserializing, so invalid discriminants cause an
+/// `InvalidTag` error.
+///
+/// Unions are supported, too, but they must be declared `zero_copy` (and thus
+/// `repr(C)`), and all their fields must be zero-copy. Since there is no way
+/// to know which field is in use, every bit pattern is accepted when
+/// ε-copy deserializing.
 ///
 /// If you do not specify `zero_copy`, the macro assumes your structure is deep-copy.
 /// However, if you have a structure that could be zero-copy, but has no attribute,
-/// a warning will be issued every time you serialize. The warning can be silenced adding
+/// a warning will be issued the first time you serialize it (see the module
+/// `epserde::ser::diagnostics` to customize this behavior). If the structure has no
+/// parameters, a deprecation warning pointing at the structure will be issued
+/// at compile time, too. The warnings can be silenced adding
 /// the explicity attribute `deep_copy`.
-#[proc_macro_derive(Epserde, attributes(zero_copy, deep_copy))]
+///
+/// Fields of `zero_copy` types must be zero-copy: otherwise, the type will
+/// not compile.
+///
+/// Misuses of the attributes are reported as compile errors pointing at the
+/// offending attribute or field.
+///
+/// Lifetime parameters are replaced by the deserialization lifetime in the
+/// deserialization type, and fields whose type mentions a lifetime parameter
+/// are ε-copy deserialized: for example, a structure `Data<'a>` with a field
+/// of type `Cow<'a, [u32]>` has deserialization type `Data<'b>`, where
+/// `'b` is the lifetime of the serialized data, and the field is a
+/// `Cow::Borrowed` pointing into the data.
+///
+/// Type parameters of deep-copy types are replaced by their deserialization
+/// types in the deserialization type if they are the type of a field.
+/// With the attribute `#[epserde(nested_params)]`, they are replaced also if
+/// they are not declared `DeepCopy` or `ZeroCopy` and all fields mentioning them
+/// are built from them using only vectors, boxed slices, arrays, tuples, and
+/// options: for example, a structure `Data<T>` with a field of type `Vec<T>`
+/// has deserialization type `Data<<T as DeserializeInner>::DeserType<'a>>`,
+/// and the field is ε-copy deserialized. Since the deserialization type of a
+/// vector of zero-copy types is a slice, such a structure can be
+/// deserialized only if `T` is deep-copy.
+///
+/// The attribute `#[epserde(zero_copy_option)]` can be used on zero-copy types
+/// to make `Option` of the type zero-copy, too. It is checked at compile time
+/// that `None` is represented by zero bytes (e.g., for a `repr(C)` structure
+/// containing a single non-zero integer, using the current compiler). Since the
+/// language does not guarantee the position of niches in user-defined
+/// structures, such code might stop compiling with a future compiler.
+///
+/// Variants of deep-copy enums are identified by a tag, which is the explicit
+/// discriminant of the variant (which must be an integer literal), or the
+/// value given by the attribute `#[epserde(tag_value = N)]`; otherwise, as for
+/// discriminants, it is the tag of the previous variant plus one. Thus,
+/// variants with explicit tags can be reordered without affecting
+/// serialized data. Tags are serialized using the smallest unsigned integer
+/// type that can represent them, unless a type is specified with the attribute
+/// `#[epserde(tag = "u16")]`.
+///
+/// Fields of deep-copy structures support the following attributes:
+///
+/// - `#[epserde(skip)]`: the field is not serialized, and it is initialized
+///   with `Default::default()` upon deserialization, or with the function
+///   specified by `#[epserde(default = "path")]`;
+///
+/// - `#[epserde(with = "module")]`: the field is serialized by
+///   `module::serialize(&value, backend)` and deserialized (both in full and
+///   in ε-copy deserialization) by `module::deserialize(backend)`; it is useful
+///   for types from other crates that do not implement ε-serde traits. The
+///   type hash contains the name of the field, the last segment of the path of
+///   the module, and the type of the field as written, with paths replaced by
+///   their last segment;
+///
+/// - `#[epserde(rename = "name")]`: the field is named `name` in type hashes,
+///   which makes it possible to rename a field without affecting existing
+///   serialized data (this attribute is available for zero-copy structures, too);
+///
+/// - `#[epserde(align = N)]`: the zero-copy data contained in the field (e.g.,
+///   the content of a vector of zero-copy elements) is aligned to at least `N`
+///   bytes, which must be a power of two, with respect to the start of the
+///   serialized data; for example, 64 aligns to cache lines, and 4096 aligns to
+///   pages (which is meaningful when memory mapping). The alignment is part of
+///   the representation hash.
+///
+/// The attribute `#[epserde(deser_struct)]` on a deep-copy structure `Foo`
+/// without lifetime parameters generates companion structures `FooDeser<'a>`
+/// and `FooDeserMut<'a>`, with the same visibility and type parameters of
+/// `Foo`, in which each field is replaced by its (mutable) deserialization type;
+/// they are used as (mutable) deserialization types, so all fields are ε-copy
+/// deserialized, even if their type is not a type parameter.
+#[proc_macro_derive(Epserde, attributes(zero_copy, deep_copy, epserde))]
 pub fn epserde_derive(input: TokenStream) -> TokenStream {
-    // Cloning input for type hash
-    let input_for_typehash = input.clone();
     let derive_input = parse_macro_input!(input as DeriveInput);
-    let (is_repr_c, is_zero_copy, is_deep_copy) = check_attrs(&derive_input);
+    epserde(derive_input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate the implementations of the derive macro `Epserde`.
+fn epserde(derive_input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    // Cloning input for type hash
+    let input_for_typehash = derive_input.clone();
+    let (is_repr_c, is_zero_copy, is_deep_copy, is_zero_copy_option) = check_attrs(&derive_input)?;
+    let option_copy = if is_zero_copy_option {
+        quote!(epserde::traits::Zero)
+    } else {
+        quote!(epserde::traits::Deep)
+    };
 
     // Common values between serialize and deserialize
     let CommonDeriveInput {
@@ -185,58 +1130,234 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
         ..
     } = CommonDeriveInput::new(derive_input.clone(), vec![]);
 
+    // Lifetime parameters are replaced by the deserialization lifetime
+    // in the deserialization type.
+    let lifetimes_raw = derive_input
+        .generics
+        .lifetimes()
+        .map(|l| l.lifetime.to_string())
+        .collect::<Vec<_>>();
+
     let out = match derive_input.data {
         Data::Struct(s) => {
+            let deser_struct_path = deser_struct_path(&derive_input.attrs);
+            let deser_struct = deser_struct_path.is_some();
+            if let Some(path) = &deser_struct_path {
+                if is_zero_copy {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        format!(
+                            "type {} is zero copy, so it cannot have a deserialization structure",
+                            name
+                        ),
+                    ));
+                }
+                if !lifetimes_raw.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        format!("type {} has lifetime parameters, so it cannot have a deserialization structure", name),
+                    ));
+                }
+            }
+            let fields_attrs = s
+                .fields
+                .iter()
+                .map(FieldAttrs::new)
+                .collect::<syn::Result<Vec<_>>>()?;
+            // Fields of the deserialization structures
+            let mut deser_struct_fields = vec![];
+            let mut deser_mut_struct_fields = vec![];
+            // Fields serialized by ε-serde
             let mut fields_types = vec![];
             let mut fields_names = vec![];
             let mut non_generic_fields = vec![];
             let mut non_generic_types = vec![];
+            // Types of fully deserialized fields
+            let mut full_types = vec![];
             let mut generic_fields = vec![];
             let mut generic_types = vec![];
+            // Serialization statements and deserialization initializers
+            // of all fields, in order.
+            let mut fields_ser = vec![];
+            let mut fields_full_des = vec![];
+            let mut fields_eps_des = vec![];
+            let mut fields_eps_mut_des = vec![];
+
+            // Type parameters replaced by their deserialization types. With
+            // deserialization structures, only the types of generic fields
+            // are relevant.
+            let substituted = if deser_struct {
+                generics_names_raw
+                    .iter()
+                    .filter(|x| {
+                        s.fields
+                            .iter()
+                            .any(|f| f.ty.to_token_stream().to_string() == **x)
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>()
+            } else {
+                let mut serialized_types = vec![];
+                let mut custom_types = vec![];
+                s.fields
+                    .iter()
+                    .zip(&fields_attrs)
+                    .for_each(|(field, attrs)| {
+                        if attrs.is_custom() {
+                            custom_types.push(&field.ty);
+                        } else {
+                            serialized_types.push(&field.ty);
+                        }
+                    });
+                substituted_type_params(
+                    &derive_input.generics,
+                    &serialized_types,
+                    &custom_types,
+                    nested_params_path(&derive_input.attrs).is_some(),
+                )
+            };
+            // Type parameters whose nested occurrences are replaced
+            let nested_params = if deser_struct || is_zero_copy {
+                vec![]
+            } else {
+                substituted.clone()
+            };
 
-            // Scan the struct to find which fields are generics, and which are not.
-            s.fields.iter().enumerate().for_each(|(field_idx, field)| {
+            // Scan the struct to find which fields are generics, and which are not,
+            // and assign ε-copy deserialization or full deserialization to
+            // fields depending whether they are generic or not.
+            for (field_idx, (field, attrs)) in s.fields.iter().zip(fields_attrs).enumerate() {
                 let ty = &field.ty;
                 let field_name = field
                     .ident
                     .to_owned()
                     .map(|x| x.to_token_stream())
                     .unwrap_or_else(|| syn::Index::from(field_idx).to_token_stream());
+                let field_name_raw = self::field_name(field_idx, field);
+                let is_generic = generics_names_raw.contains(&ty.to_token_stream().to_string());
+
+                let vis = &field.vis;
+                let ident = field.ident.as_ref().map(|ident| quote!(#ident:));
+                if attrs.is_custom() {
+                    deser_struct_fields.push(quote!(#vis #ident #ty));
+                    deser_mut_struct_fields.push(quote!(#vis #ident #ty));
+                } else {
+                    deser_struct_fields.push(quote!(#vis #ident <#ty as epserde::deser::DeserializeInner>::DeserType<'epserde_desertype>));
+                    deser_mut_struct_fields.push(quote!(#vis #ident <#ty as epserde::deser::DeserializeMutInner>::DeserMutType<'epserde_desertype>));
+                }
+
+                if attrs.is_custom() {
+                    if is_zero_copy {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "the field cannot be skipped or serialized with a custom module, as the type is zero copy",
+                        ));
+                    }
+                    if is_generic {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "the field cannot be skipped or serialized with a custom module, as its type is a type parameter",
+                        ));
+                    }
+                    if let Some(module) = &attrs.with {
+                        fields_ser.push(quote!(#module::serialize(&self.#field_name, backend)?;));
+                        let init = quote!(#field_name: #module::deserialize(backend)?);
+                        fields_full_des.push(init.clone());
+                        fields_eps_des.push(init.clone());
+                        fields_eps_mut_des.push(init);
+                    } else {
+                        let init = match &attrs.default {
+                            Some(default) => quote!(#field_name: #default()),
+                            None => quote!(#field_name: core::default::Default::default()),
+                        };
+                        fields_full_des.push(init.clone());
+                        fields_eps_des.push(init.clone());
+                        fields_eps_mut_des.push(init);
+                    }
+                    continue;
+                }
+
+                if is_zero_copy && attrs.align.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "the field cannot be aligned, as the type is zero copy",
+                    ));
+                }
 
-                if generics_names_raw.contains(&ty.to_token_stream().to_string()) {
+                if is_generic {
                     generic_fields.push(field_name.clone());
                     generic_types.push(ty);
                 } else {
                     non_generic_fields.push(field_name.clone());
                     non_generic_types.push(ty);
                 }
-                fields_types.push(ty);
-                fields_names.push(field_name);
-            });
-
-            // Assign  ε-copy deserialization or full deserialization to
-            // fields depending whether they are generic or not.
-            let mut methods: Vec<proc_macro2::TokenStream> = vec![];
 
-            s.fields.iter().for_each(|field| {
-                let ty = &field.ty;
-                if generics_names_raw.contains(&ty.to_token_stream().to_string()) {
-                    methods.push(syn::parse_quote!(_deserialize_eps_inner));
+                let write_name = attrs.rename.unwrap_or(field_name_raw);
+                let (eps_des, eps_mut_des) = if is_generic
+                    || is_nested_generic(ty, &nested_params)
+                    || mentions_lifetimes(ty, &lifetimes_raw)
+                    || deser_struct
+                {
+                    (
+                        quote!(<#ty>::_deserialize_eps_inner(backend)),
+                        quote!(<#ty>::_deserialize_eps_mut_inner(backend)),
+                    )
                 } else {
-                    methods.push(syn::parse_quote!(_deserialize_full_inner));
+                    full_types.push(ty);
+                    (
+                        quote!(<#ty>::_deserialize_full_inner(backend)),
+                        quote!(<#ty>::_deserialize_full_inner(backend)),
+                    )
+                };
+                // Aligned fields raise temporarily the minimum alignment of the backend
+                match attrs.align {
+                    Some(align) => {
+                        fields_ser.push(quote!(epserde::ser::helpers::with_min_align(backend, #align, |backend| backend.write(#write_name, &self.#field_name))?;));
+                        fields_full_des.push(quote!(#field_name: epserde::deser::helpers::with_min_align(backend, #align, |backend| <#ty>::_deserialize_full_inner(backend))?));
+                        fields_eps_des.push(quote!(#field_name: epserde::deser::helpers::with_min_align(backend, #align, |backend| #eps_des)?));
+                        fields_eps_mut_des.push(quote!(#field_name: epserde::deser::helpers::with_min_align(backend, #align, |backend| #eps_mut_des)?));
+                    }
+                    None => {
+                        fields_ser.push(quote!(backend.write(#write_name, &self.#field_name)?;));
+                        fields_full_des
+                            .push(quote!(#field_name: <#ty>::_deserialize_full_inner(backend)?));
+                        fields_eps_des.push(quote!(#field_name: #eps_des?));
+                        fields_eps_mut_des.push(quote!(#field_name: #eps_mut_des?));
+                    }
                 }
-            });
+
+                fields_types.push(ty);
+                fields_names.push(field_name);
+            }
+
+            // Types with custom fields cannot be zero-copy
+            let has_custom_fields = fields_types.len() != s.fields.len();
+            let is_repr_c = is_repr_c && !has_custom_fields;
+            let is_deep_copy = is_deep_copy || has_custom_fields;
 
             // Gather deserialization types of fields,
             // which are necessary to derive the deserialization type.
             let deser_type_generics = generics_name_vec
                 .iter()
                 .map(|ty| {
-                    if generic_types
-                        .iter()
-                        .any(|x| x.to_token_stream().to_string() == ty.to_string())
-                    {
+                    if substituted.contains(&ty.to_string()) {
                         quote!(<#ty as epserde::deser::DeserializeInner>::DeserType<'epserde_desertype>)
+                    } else if lifetimes_raw.contains(&ty.to_string()) {
+                        quote!('epserde_desertype)
+                    } else {
+                        ty.clone()
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            // Same for mutable deserialization types.
+            let deser_mut_type_generics = generics_name_vec
+                .iter()
+                .map(|ty| {
+                    if substituted.contains(&ty.to_string()) {
+                        quote!(<#ty as epserde::deser::DeserializeMutInner>::DeserMutType<'epserde_desertype>)
+                    } else if lifetimes_raw.contains(&ty.to_string()) {
+                        quote!('epserde_desertype)
                     } else {
                         ty.clone()
                     }
@@ -268,67 +1389,163 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                         bounds: bounds_ser,
                     }));
                 // add that every struct field has to implement DeserializeInner
-                let mut bounds_des = Punctuated::new();
-                bounds_des.push(syn::parse_quote!(epserde::deser::DeserializeInner));
                 where_clause_des
                     .predicates
-                    .push(WherePredicate::Type(PredicateType {
-                        lifetimes: None,
-                        bounded_ty: (*ty).clone(),
-                        colon_token: token::Colon::default(),
-                        bounds: bounds_des,
-                    }));
+                    .push(deser_bound(ty, &lifetimes_raw, &nested_params));
+            });
+
+            // add that substituted type parameters implement DeserializeInner,
+            // as they might appear only in nested generic fields
+            nested_params.iter().for_each(|param| {
+                let param = syn::Ident::new(param, proc_macro2::Span::call_site());
+                where_clause_des
+                    .predicates
+                    .push(syn::parse_quote!(#param: epserde::deser::DeserializeInner));
             });
 
             // We add to the deserialization where clause the bounds on the deserialization
-            // types of the fields derived from the bounds of the original types of the fields.
-            // TODO: we presently handle only inlined bounds, and not bounds in a where clause.
-            derive_input.generics.params.iter().for_each(|param| {
-                if let GenericParam::Type(t) = param {
-                    let ty = &t.ident;
-
-                    // Skip generics not involved in deserialization type substitution.
-                    if t.bounds.is_empty() || ! generic_types
-                        .iter()
-                        .any(|x| *ty == x.to_token_stream().to_string())
-                    {
-                        return;
+            // types of the fields derived from the bounds of the original types of the fields,
+            // both inlined and in the where clause.
+            generic_bounds(&derive_input.generics, &substituted)
+                .iter()
+                .for_each(|predicate| {
+                    where_clause_des
+                        .predicates
+                        .push(deser_type_bound(predicate, false));
+                });
+
+            let where_clause_des_mut = where_clause_des_mut(
+                &where_clause_des,
+                &derive_input.generics,
+                &substituted,
+                &fields_types
+                    .iter()
+                    .copied()
+                    .filter(|ty| is_nested_generic(ty, &nested_params))
+                    .collect::<Vec<_>>(),
+                if is_zero_copy { &[] } else { &full_types },
+            );
+
+            // The deserialization structures, if requested, replace each
+            // field with its deserialization type.
+            let (deser_name, deser_type, deser_mut_name, deser_mut_type, deser_structs) =
+                if deser_struct {
+                    if fields_types.is_empty() {
+                        return Err(syn::Error::new_spanned(
+                        deser_struct_path,
+                        format!("type {} has no serialized field, so it cannot have a deserialization structure", name),
+                    ));
                     }
+                    let vis = &derive_input.vis;
+                    let deser_name = syn::Ident::new(&format!("{}Deser", name), name.span());
+                    let deser_mut_name = syn::Ident::new(&format!("{}DeserMut", name), name.span());
+                    let deser_type = quote!(#deser_name<'epserde_desertype, #generics_names>);
+                    let deser_mut_type =
+                        quote!(#deser_mut_name<'epserde_desertype, #generics_names>);
+                    let mut where_clause = where_clause.clone();
+                    let mut where_clause_mut = where_clause.clone();
+                    fields_types.iter().for_each(|ty| {
+                        where_clause
+                            .predicates
+                            .push(syn::parse_quote!(#ty: epserde::deser::DeserializeInner));
+                        where_clause_mut
+                            .predicates
+                            .push(syn::parse_quote!(#ty: epserde::deser::DeserializeMutInner));
+                    });
+                    let doc = format!("The ε-copy deserialization type of [`{}`].", name);
+                    let doc_mut =
+                        format!("The mutable ε-copy deserialization type of [`{}`].", name);
+                    let structs = if matches!(s.fields, syn::Fields::Unnamed(_)) {
+                        quote! {
+                            #[doc = #doc]
+                            #vis struct #deser_name<'epserde_desertype, #generics>(#(#deser_struct_fields,)*) #where_clause;
+
+                            #[doc = #doc_mut]
+                            #vis struct #deser_mut_name<'epserde_desertype, #generics>(#(#deser_mut_struct_fields,)*) #where_clause_mut;
+                        }
+                    } else {
+                        quote! {
+                            #[doc = #doc]
+                            #vis struct #deser_name<'epserde_desertype, #generics> #where_clause {
+                                #(#deser_struct_fields,)*
+                            }
 
-                    // add a lifetime so we express bounds on DeserType
-                    let mut lifetimes = Punctuated::new();
-                    lifetimes.push(GenericParam::Lifetime(LifetimeParam {
-                        attrs: vec![],
-                        lifetime: syn::Lifetime::new("'epserde_desertype", proc_macro2::Span::call_site()),
-                        colon_token: None,
-                        bounds: Punctuated::new(),
-                    }));
-                    // add that the DeserType is a DeserializeInner
-                    where_clause_des
+                            #[doc = #doc_mut]
+                            #vis struct #deser_mut_name<'epserde_desertype, #generics> #where_clause_mut {
+                                #(#deser_mut_struct_fields,)*
+                            }
+                        }
+                    };
+                    (
+                        deser_name,
+                        deser_type,
+                        deser_mut_name,
+                        deser_mut_type,
+                        structs,
+                    )
+                } else {
+                    (
+                        name.clone(),
+                        quote!(#name<#(#deser_type_generics,)*>),
+                        name.clone(),
+                        quote!(#name<#(#deser_mut_type_generics,)*>),
+                        quote!(),
+                    )
+                };
+
+            // The mutable deserialization structure requires all fields
+            // to support mutable deserialization.
+            let mut where_clause_des_mut = where_clause_des_mut;
+            if deser_struct {
+                fields_types.iter().for_each(|ty| {
+                    where_clause_des_mut
                         .predicates
-                        .push(WherePredicate::Type(PredicateType {
-                            lifetimes: Some(BoundLifetimes {
-                                for_token: token::For::default(),
-                                lt_token: token::Lt::default(),
-                                lifetimes,
-                                gt_token: token::Gt::default(),
-                            }),
-                            bounded_ty: syn::parse_quote!(
-                                <#ty as epserde::deser::DeserializeInner>::DeserType<'epserde_desertype>
-                            ),
-                            colon_token: token::Colon::default(),
-                            bounds: t.bounds.clone(),
-                        }));
-                }
-            });
+                        .push(syn::parse_quote!(#ty: epserde::deser::DeserializeMutInner));
+                });
+            }
+
+            // For a single field, zero-copy options are valid exactly when
+            // they are valid for the field.
+            let must_check_zero_copy_option = if fields_types.len() == 1 {
+                let ty = fields_types[0];
+                quote!(const MUST_CHECK_ZERO_COPY_OPTION: bool =
+                    <#ty as epserde::deser::DeserializeInner>::MUST_CHECK_ZERO_COPY_OPTION;)
+            } else {
+                quote!()
+            };
+
+            let mismatch_lint =
+                zero_copy_mismatch_lint(&name, &derive_input.generics, is_deep_copy, &fields_types);
 
             if is_zero_copy {
+                let mut where_clause_ser = where_clause_ser;
+                let mut where_clause_des = where_clause_des;
+                let mut where_clause_des_mut = where_clause_des_mut;
+                add_zero_copy_bounds(&mut where_clause_ser, &fields_types);
+                add_zero_copy_bounds(&mut where_clause_des, &fields_types);
+                add_zero_copy_bounds(&mut where_clause_des_mut, &fields_types);
+                let fields_check = zero_copy_fields_check(
+                    &name,
+                    &derive_input.generics,
+                    &fields_names
+                        .iter()
+                        .map(|field| format!("field `{}`", field))
+                        .zip(&fields_types)
+                        .collect::<Vec<_>>(),
+                );
                 quote! {
+                    #fields_check
+
                     #[automatically_derived]
                     impl<#generics> epserde::traits::CopyType for  #name<#generics_names> #where_clause {
                         type Copy = epserde::traits::Zero;
                     }
 
+                    #[automatically_derived]
+                    impl<#generics> epserde::traits::ZeroOptionCopyType for  #name<#generics_names> #where_clause {
+                        type OptionCopy = #option_copy;
+                    }
+
                     #[automatically_derived]
                     impl<#generics_serialize> epserde::ser::SerializeInner for #name<#generics_names> #where_clause_ser {
                         // Compute whether the type could be zero copy
@@ -341,11 +1558,6 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                         #[inline(always)]
                         fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
-                            // No-op code that however checks that all fields are zero-copy.
-                            fn test<T: epserde::traits::ZeroCopy>() {}
-                            #(
-                                test::<#fields_types>();
-                            )*
                             epserde::ser::helpers::serialize_zero(backend, self)
                         }
                     }
@@ -353,6 +1565,13 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                     #[automatically_derived]
                     impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des
                     {
+                        // Some field might have invalid bit patterns.
+                        const MUST_CHECK_ZERO_COPY: bool = false #(
+                            || <#fields_types as epserde::deser::DeserializeInner>::MUST_CHECK_ZERO_COPY
+                        )*;
+
+                        #must_check_zero_copy_option
+
                         fn _deserialize_full_inner(
                             backend: &mut impl epserde::deser::ReadWithPos,
                         ) -> core::result::Result<Self, epserde::deser::Error> {
@@ -362,16 +1581,41 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                         type DeserType<'epserde_desertype> = &'epserde_desertype #name<#generics_names>;
 
-                        fn _deserialize_eps_inner<'a>(
-                            backend: &mut epserde::deser::SliceWithPos<'a>,
-                        ) -> core::result::Result<Self::DeserType<'a>, epserde::deser::Error>
+                        fn _deserialize_eps_inner<'epserde_desertype>(
+                            backend: &mut epserde::deser::SliceWithPos<'epserde_desertype>,
+                        ) -> core::result::Result<Self::DeserType<'epserde_desertype>, epserde::deser::Error>
                         {
                             epserde::deser::helpers::deserialize_eps_zero::<Self>(backend)
                         }
+
+                        fn _check_zero_copy(bytes: &[u8]) -> core::result::Result<(), epserde::deser::Error> {
+                            // Check each field at its offset
+                            #(
+                                <#fields_types as epserde::deser::DeserializeInner>::_check_zero_copy(
+                                    &bytes[core::mem::offset_of!(Self, #fields_names)..][..core::mem::size_of::<#fields_types>()]
+                                )?;
+                            )*
+                            Ok(())
+                        }
+                    }
+
+                    #[automatically_derived]
+                    impl<#generics_deserialize> epserde::deser::DeserializeMutInner for #name<#generics_names> #where_clause_des_mut
+                    {
+                        type DeserMutType<'epserde_desertype> = &'epserde_desertype mut #name<#generics_names>;
+
+                        fn _deserialize_eps_mut_inner<'epserde_desertype>(
+                            backend: &mut epserde::deser::SliceWithPosMut<'epserde_desertype>,
+                        ) -> core::result::Result<Self::DeserMutType<'epserde_desertype>, epserde::deser::Error>
+                        {
+                            epserde::deser::helpers::deserialize_eps_mut_zero::<Self>(backend)
+                        }
                     }
                 }
             } else {
                 quote! {
+                    #mismatch_lint
+
                     #[automatically_derived]
                     impl<#generics> epserde::traits::CopyType for  #name<#generics_names> #where_clause {
                         type Copy = epserde::traits::Deep;
@@ -390,9 +1634,9 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                         #[inline(always)]
                         fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
-                            epserde::ser::helpers::check_mismatch::<Self>();
+                            epserde::ser::helpers::check_mismatch::<Self>(backend)?;
                             #(
-                                backend.write(stringify!(#fields_names), &self.#fields_names)?;
+                                #fields_ser
                             )*
                             Ok(())
                         }
@@ -406,25 +1650,45 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                             use epserde::deser::DeserializeInner;
                             Ok(#name{
                                 #(
-                                    #fields_names: <#fields_types>::_deserialize_full_inner(backend)?,
+                                    #fields_full_des,
                                 )*
                             })
                         }
 
-                        type DeserType<'epserde_desertype> = #name<#(#deser_type_generics,)*>;
+                        type DeserType<'epserde_desertype> = #deser_type;
 
-                        fn _deserialize_eps_inner<'a>(
-                            backend: &mut epserde::deser::SliceWithPos<'a>,
-                        ) -> core::result::Result<Self::DeserType<'a>, epserde::deser::Error>
+                        fn _deserialize_eps_inner<'epserde_desertype>(
+                            backend: &mut epserde::deser::SliceWithPos<'epserde_desertype>,
+                        ) -> core::result::Result<Self::DeserType<'epserde_desertype>, epserde::deser::Error>
                         {
                             use epserde::deser::DeserializeInner;
-                            Ok(#name{
+                            Ok(#deser_name{
+                                #(
+                                    #fields_eps_des,
+                                )*
+                            })
+                        }
+                    }
+
+                    #[automatically_derived]
+                    impl<#generics_deserialize> epserde::deser::DeserializeMutInner for #name<#generics_names> #where_clause_des_mut {
+                        type DeserMutType<'epserde_desertype> = #deser_mut_type;
+
+                        fn _deserialize_eps_mut_inner<'epserde_desertype>(
+                            backend: &mut epserde::deser::SliceWithPosMut<'epserde_desertype>,
+                        ) -> core::result::Result<Self::DeserMutType<'epserde_desertype>, epserde::deser::Error>
+                        {
+                            use epserde::deser::DeserializeInner;
+                            use epserde::deser::DeserializeMutInner;
+                            Ok(#deser_mut_name{
                                 #(
-                                    #fields_names: <#fields_types>::#methods(backend)?,
+                                    #fields_eps_mut_des,
                                 )*
                             })
                         }
                     }
+
+                    #deser_structs
                 }
             }
         }
@@ -445,27 +1709,98 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
             let mut where_clause_des = where_clause.clone();
             let mut variant_full_des = Vec::new();
             let mut variant_eps_des = Vec::new();
+            let mut variant_eps_mut_des = Vec::new();
             let mut generic_types = Vec::new();
             let mut generic_fields = Vec::new();
             let mut non_generic_fields = Vec::new();
             let mut non_generic_types = Vec::new();
             let mut fields_types = Vec::new();
+            // Types of the fields of each variant, and discriminants,
+            // used to check zero-copy enums.
+            let mut variants_fields_types = Vec::new();
+            let mut discriminants = Vec::new();
+            // Descriptions and types of the fields of all variants, used
+            // to report fields of zero-copy enums that are not zero-copy.
+            let mut variants_fields = Vec::new();
+            // Tags written to identify variants
+            let (tag_values, tag_type) = if is_zero_copy {
+                if let Some(attr) = e
+                    .variants
+                    .iter()
+                    .flat_map(|v| v.attrs.iter())
+                    .find(|x| x.meta.path().is_ident("epserde"))
+                {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        format!(
+                            "type {} is zero copy: use discriminants instead of tag values",
+                            name
+                        ),
+                    ));
+                }
+                (
+                    vec![],
+                    syn::Ident::new("u8", proc_macro2::Span::call_site()),
+                )
+            } else {
+                enum_tags(&derive_input.attrs, &e)?
+            };
+            for variant in &e.variants {
+                check_no_field_attrs(&variant.fields)?;
+            }
+            let tag_values = tag_values
+                .iter()
+                .map(|tag| {
+                    syn::LitInt::new(
+                        &format!("{}{}", tag, tag_type),
+                        proc_macro2::Span::call_site(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            // Type parameters replaced by their deserialization types
+            let substituted = substituted_type_params(
+                &derive_input.generics,
+                &e.variants
+                    .iter()
+                    .flat_map(|variant| variant.fields.iter().map(|field| &field.ty))
+                    .collect::<Vec<_>>(),
+                &[],
+                nested_params_path(&derive_input.attrs).is_some(),
+            );
+            // Type parameters whose nested occurrences are replaced
+            let nested_params = if is_zero_copy {
+                vec![]
+            } else {
+                substituted.clone()
+            };
+            // Nested generic types of fields
+            let mut nested_types = Vec::new();
+            // Types of fully deserialized fields
+            let mut full_types = Vec::new();
             e.variants.iter().enumerate().for_each(|(variant_id, variant)| {                    
+                let tag_value = tag_values.get(variant_id);
                 variants_names.push(variant.ident.to_token_stream());
+                let ident = &variant.ident;
+                discriminants.push(match &variant.discriminant {
+                    Some((_, expr)) => quote!(#ident = #expr),
+                    None => quote!(#ident),
+                });
                 match &variant.fields {
                 syn::Fields::Unit => {
                     variants.push(variant.ident.to_token_stream());
                     variant_ser.push(quote! {{                        
-                        backend.write("tag", &#variant_id)?;
+                        backend.write("tag", &#tag_value)?;
                     }});
-                    variant_full_des.push(quote! {{}});
-                    variant_eps_des.push(quote! {{}});
+                    variant_full_des.push(quote! {});
+                    variant_eps_des.push(quote! {});
+                    variant_eps_mut_des.push(quote! {});
+                    variants_fields_types.push(vec![]);
                 }
                 syn::Fields::Named(fields) => {
-                    variants_names.push(variant.ident.to_token_stream());
                     let mut var_fields_names = Vec::new();
                     let mut var_fields_types = Vec::new();
                     let mut methods: Vec<proc_macro2::TokenStream> = vec![];
+                    let mut methods_mut: Vec<proc_macro2::TokenStream> = vec![];
                     fields
                         .named
                         .iter()
@@ -482,6 +1817,10 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                             var_fields_names.push(ident.to_token_stream());
                             var_fields_types.push(ty.to_token_stream());
+                            variants_fields.push((
+                                format!("field `{}` of variant `{}`", ident, variant.ident),
+                                ty.to_token_stream(),
+                            ));
 
                             // add that every struct field has to implement SerializeInner
                             let mut bounds_ser = Punctuated::new();
@@ -495,21 +1834,25 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                                     bounds: bounds_ser,
                             }));
                             // add that every struct field has to implement DeserializeInner
-                            let mut bounds_des = Punctuated::new();
-                            bounds_des.push(syn::parse_quote!(epserde::deser::DeserializeInner));
                             where_clause_des
                                 .predicates
-                                .push(WherePredicate::Type(PredicateType {
-                                    lifetimes: None,
-                                    bounded_ty: ty.clone(),
-                                    colon_token: token::Colon::default(),
-                                    bounds: bounds_des,
-                            }));
+                                .push(deser_bound(ty, &lifetimes_raw, &nested_params));
 
-                            if generics_names_raw.contains(&ty.to_token_stream().to_string()) {
+                            let is_nested = is_nested_generic(ty, &nested_params);
+                            if is_nested {
+                                nested_types.push(ty);
+                            }
+
+                            if generics_names_raw.contains(&ty.to_token_stream().to_string())
+                                || is_nested
+                                || mentions_lifetimes(ty, &lifetimes_raw)
+                            {
                                 methods.push(syn::parse_quote!(_deserialize_eps_inner));
+                                methods_mut.push(syn::parse_quote!(_deserialize_eps_mut_inner));
                             } else {
+                                full_types.push(ty);
                                 methods.push(syn::parse_quote!(_deserialize_full_inner));
+                                methods_mut.push(syn::parse_quote!(_deserialize_full_inner));
                             }
                         });
                     let ident = variant.ident.clone();
@@ -517,8 +1860,9 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                         #ident{ #( #var_fields_names, )* }
                     });
                     fields_types.extend(var_fields_types.clone());
+                    variants_fields_types.push(var_fields_types.clone());
                     variant_ser.push(quote! {
-                        backend.write("tag", &#variant_id)?;
+                        backend.write("tag", &#tag_value)?;
                         #(
                             backend.write(stringify!(#var_fields_names), #var_fields_names)?;
                         )*
@@ -533,12 +1877,18 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                             #var_fields_names: <#var_fields_types>::#methods(backend)?,
                         )*
                     });
+                    variant_eps_mut_des.push(quote! {
+                        #(
+                            #var_fields_names: <#var_fields_types>::#methods_mut(backend)?,
+                        )*
+                    });
                 }
                 syn::Fields::Unnamed(fields) => {
                     let mut var_fields_names = Vec::new();
                     let mut var_fields_vars = Vec::new();
                     let mut var_fields_types = Vec::new();
                     let mut methods: Vec<proc_macro2::TokenStream> = vec![];
+                    let mut methods_mut: Vec<proc_macro2::TokenStream> = vec![];
 
                     fields
                         .unnamed
@@ -562,6 +1912,10 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                             .to_token_stream());
                             var_fields_vars.push(syn::Index::from(field_idx));
                             var_fields_types.push(ty.to_token_stream());
+                            variants_fields.push((
+                                format!("field `{}` of variant `{}`", field_idx, variant.ident),
+                                ty.to_token_stream(),
+                            ));
 
 
                             // add that every struct field has to implement SerializeInner
@@ -576,21 +1930,25 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                                     bounds: bounds_ser,
                             }));
                             // add that every struct field has to implement DeserializeInner
-                            let mut bounds_des = Punctuated::new();
-                            bounds_des.push(syn::parse_quote!(epserde::deser::DeserializeInner));
                             where_clause_des
                                 .predicates
-                                .push(WherePredicate::Type(PredicateType {
-                                    lifetimes: None,
-                                    bounded_ty: ty.clone(),
-                                    colon_token: token::Colon::default(),
-                                    bounds: bounds_des,
-                            }));
+                                .push(deser_bound(ty, &lifetimes_raw, &nested_params));
 
-                            if generics_names_raw.contains(&ty.to_token_stream().to_string()) {
+                            let is_nested = is_nested_generic(ty, &nested_params);
+                            if is_nested {
+                                nested_types.push(ty);
+                            }
+
+                            if generics_names_raw.contains(&ty.to_token_stream().to_string())
+                                || is_nested
+                                || mentions_lifetimes(ty, &lifetimes_raw)
+                            {
                                 methods.push(syn::parse_quote!(_deserialize_eps_inner));
+                                methods_mut.push(syn::parse_quote!(_deserialize_eps_mut_inner));
                             } else {
+                                full_types.push(ty);
                                 methods.push(syn::parse_quote!(_deserialize_full_inner));
+                                methods_mut.push(syn::parse_quote!(_deserialize_full_inner));
                             }
 
                         });
@@ -600,9 +1958,10 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                         #ident( #( #var_fields_names, )* )
                     });
                     fields_types.extend(var_fields_types.clone());
+                    variants_fields_types.push(var_fields_types.clone());
 
                     variant_ser.push(quote! {
-                        backend.write("tag", &#variant_id)?;
+                        backend.write("tag", &#tag_value)?;
                         #(
                             backend.write(stringify!(#var_fields_names), #var_fields_names)?;
                         )*
@@ -617,6 +1976,11 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                             #var_fields_vars    : <#var_fields_types>::#methods(backend)?,
                         )*
                     });
+                    variant_eps_mut_des.push(quote! {
+                        #(
+                            #var_fields_vars    : <#var_fields_types>::#methods_mut(backend)?,
+                        )*
+                    });
                 }
             }});
 
@@ -625,26 +1989,156 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
             let deser_type_generics = generics_name_vec
                 .iter()
                 .map(|ty| {
-                    if generic_types
-                        .iter()
-                        .any(|x| x.to_token_stream().to_string() == ty.to_string())
-                    {
+                    if substituted.contains(&ty.to_string()) {
                         quote!(<#ty as epserde::deser::DeserializeInner>::DeserType<'epserde_desertype>)
+                    } else if lifetimes_raw.contains(&ty.to_string()) {
+                        quote!('epserde_desertype)
+                    } else {
+                        ty.clone()
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            // Same for mutable deserialization types.
+            let deser_mut_type_generics = generics_name_vec
+                .iter()
+                .map(|ty| {
+                    if substituted.contains(&ty.to_string()) {
+                        quote!(<#ty as epserde::deser::DeserializeMutInner>::DeserMutType<'epserde_desertype>)
+                    } else if lifetimes_raw.contains(&ty.to_string()) {
+                        quote!('epserde_desertype)
                     } else {
                         ty.clone()
                     }
                 })
                 .collect::<Vec<_>>();
 
+            // add that substituted type parameters implement DeserializeInner,
+            // as they might appear only in nested generic fields
+            nested_params.iter().for_each(|param| {
+                let param = syn::Ident::new(param, proc_macro2::Span::call_site());
+                where_clause_des
+                    .predicates
+                    .push(syn::parse_quote!(#param: epserde::deser::DeserializeInner));
+            });
+
+            // We add to the deserialization where clause the bounds on the deserialization
+            // types of the fields derived from the bounds of the original types of the fields,
+            // both inlined and in the where clause.
+            generic_bounds(&derive_input.generics, &substituted)
+                .iter()
+                .for_each(|predicate| {
+                    where_clause_des
+                        .predicates
+                        .push(deser_type_bound(predicate, false));
+                });
+
+            let where_clause_des_mut = where_clause_des_mut(
+                &where_clause_des,
+                &derive_input.generics,
+                &substituted,
+                &nested_types,
+                if is_zero_copy { &[] } else { &full_types },
+            );
+
             let tag = (0..variants.len()).collect::<Vec<_>>();
 
+            // Zero-copy enums are checked by comparing the bytes of the
+            // discriminant with the bytes of the discriminants of a fieldless
+            // enum with the same representation, and then checking the fields
+            // of the variant at the offsets prescribed by the representation.
+            let check_zero_copy = if is_zero_copy {
+                let tag_repr = int_repr(&derive_input.attrs)?
+                    .map(|x| x.to_token_stream())
+                    .unwrap_or(quote!(C));
+                // With repr(C), fields are stored in a union following the tag;
+                // otherwise, each variant is a repr(C) structure starting with the tag.
+                let payload_start = if reprs(&derive_input.attrs)?.iter().any(|x| x == "C") {
+                    quote! {{
+                        let mut align = 1;
+                        #(
+                            align = align.max(core::mem::align_of::<#fields_types>());
+                        )*
+                        TAG_SIZE.next_multiple_of(align)
+                    }}
+                } else {
+                    quote!(TAG_SIZE)
+                };
+                let variants_checks = variants_fields_types.iter().map(|var_fields_types| {
+                    quote! {
+                        #(
+                            offset = offset.next_multiple_of(core::mem::align_of::<#var_fields_types>());
+                            <#var_fields_types as epserde::deser::DeserializeInner>::_check_zero_copy(
+                                &bytes[offset..][..core::mem::size_of::<#var_fields_types>()]
+                            )?;
+                            offset += core::mem::size_of::<#var_fields_types>();
+                        )*
+                    }
+                });
+                quote! {
+                    fn _check_zero_copy(bytes: &[u8]) -> core::result::Result<(), epserde::deser::Error> {
+                        #[allow(dead_code, clippy::enum_variant_names)]
+                        #[repr(#tag_repr)]
+                        enum Discriminant {
+                            #(#discriminants,)*
+                        }
+                        const TAG_SIZE: usize = core::mem::size_of::<Discriminant>();
+                        let tag = &bytes[..TAG_SIZE];
+                        let variant = [#(Discriminant::#variants_names,)*]
+                            .iter()
+                            .position(|discriminant| {
+                                // SAFETY: fieldless enums have no padding
+                                let discriminant = unsafe {
+                                    core::slice::from_raw_parts(
+                                        discriminant as *const Discriminant as *const u8,
+                                        TAG_SIZE,
+                                    )
+                                };
+                                discriminant == tag
+                            })
+                            .ok_or_else(|| epserde::deser::Error::InvalidTag(
+                                epserde::deser::helpers::zero_copy_tag(tag)
+                            ))?;
+                        #[allow(unused_mut, unused_variables)]
+                        let mut offset: usize = #payload_start;
+                        match variant {
+                            #(
+                                #tag => { #variants_checks }
+                            )*
+                            _ => unreachable!(),
+                        }
+                        Ok(())
+                    }
+                }
+            } else {
+                quote!()
+            };
+
+            let mismatch_lint =
+                zero_copy_mismatch_lint(&name, &derive_input.generics, is_deep_copy, &fields_types);
+
             if is_zero_copy {
+                let mut where_clause_ser = where_clause_ser;
+                let mut where_clause_des = where_clause_des;
+                let mut where_clause_des_mut = where_clause_des_mut;
+                add_zero_copy_bounds(&mut where_clause_ser, &fields_types);
+                add_zero_copy_bounds(&mut where_clause_des, &fields_types);
+                add_zero_copy_bounds(&mut where_clause_des_mut, &fields_types);
+                let fields_check =
+                    zero_copy_fields_check(&name, &derive_input.generics, &variants_fields);
                 quote! {
+                    #fields_check
+
                     #[automatically_derived]
                     impl<#generics> epserde::traits::CopyType for  #name<#generics_names> #where_clause {
                         type Copy = epserde::traits::Zero;
                     }
 
+                    #[automatically_derived]
+                    impl<#generics> epserde::traits::ZeroOptionCopyType for  #name<#generics_names> #where_clause {
+                        type OptionCopy = #option_copy;
+                    }
+
                     #[automatically_derived]
                     impl<#generics_serialize> epserde::ser::SerializeInner for #name<#generics_names> #where_clause_ser {
                         // Compute whether the type could be zero copy
@@ -657,17 +2151,15 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                         #[inline(always)]
                         fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
-                            // No-op code that however checks that all fields are zero-copy.
-                            fn test<T: epserde::traits::ZeroCopy>() {}
-                            #(
-                                test::<#fields_types>();
-                            )*
                             epserde::ser::helpers::serialize_zero(backend, self)
                         }
                     }
 
                     #[automatically_derived]
                     impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des {
+                        // The discriminant must always be checked.
+                        const MUST_CHECK_ZERO_COPY: bool = true;
+
                         fn _deserialize_full_inner(
                             backend: &mut impl epserde::deser::ReadWithPos,
                         ) -> core::result::Result<Self, epserde::deser::Error> {
@@ -676,16 +2168,33 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                         type DeserType<'epserde_desertype> = &'epserde_desertype #name<#generics_names>;
 
-                        fn _deserialize_eps_inner<'a>(
-                            backend: &mut epserde::deser::SliceWithPos<'a>,
-                        ) -> core::result::Result<Self::DeserType<'a>, epserde::deser::Error>
+                        fn _deserialize_eps_inner<'epserde_desertype>(
+                            backend: &mut epserde::deser::SliceWithPos<'epserde_desertype>,
+                        ) -> core::result::Result<Self::DeserType<'epserde_desertype>, epserde::deser::Error>
                         {
                             epserde::deser::helpers::deserialize_eps_zero::<Self>(backend)
                         }
+
+                        #check_zero_copy
+                    }
+
+                    #[automatically_derived]
+                    impl<#generics_deserialize> epserde::deser::DeserializeMutInner for #name<#generics_names> #where_clause_des_mut
+                    {
+                        type DeserMutType<'epserde_desertype> = &'epserde_desertype mut #name<#generics_names>;
+
+                        fn _deserialize_eps_mut_inner<'epserde_desertype>(
+                            backend: &mut epserde::deser::SliceWithPosMut<'epserde_desertype>,
+                        ) -> core::result::Result<Self::DeserMutType<'epserde_desertype>, epserde::deser::Error>
+                        {
+                            epserde::deser::helpers::deserialize_eps_mut_zero::<Self>(backend)
+                        }
                     }
                 }
             } else {
                 quote! {
+                    #mismatch_lint
+
                     #[automatically_derived]
                     impl<#generics> epserde::traits::CopyType for  #name<#generics_names> #where_clause {
                         type Copy = epserde::traits::Deep;
@@ -704,7 +2213,7 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
 
                         #[inline(always)]
                         fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
-                            epserde::ser::helpers::check_mismatch::<Self>();
+                            epserde::ser::helpers::check_mismatch::<Self>(backend)?;
                             match self {
                                 #(
                                    Self::#variants => { #variant_ser }
@@ -720,45 +2229,254 @@ pub fn epserde_derive(input: TokenStream) -> TokenStream {
                             backend: &mut impl epserde::deser::ReadWithPos,
                         ) -> core::result::Result<Self, epserde::deser::Error> {
                             use epserde::deser::DeserializeInner;
-                            match usize::deserialize_full(backend)? {
+                            match <#tag_type>::_deserialize_full_inner(backend)? {
                                 #(
-                                    #tag => Ok(Self::#variants_names{ #variant_full_des }),
+                                    #tag_values => Ok(Self::#variants_names{ #variant_full_des }),
                                 )*
-                                tag => Err(epserde::deser::Error::InvalidTag(tag)),
+                                tag => Err(epserde::deser::Error::InvalidTag(tag as usize)),
                             }
                         }
 
                         type DeserType<'epserde_desertype> = #name<#(#deser_type_generics,)*>;
 
-                        fn _deserialize_eps_inner<'a>(
-                            backend: &mut epserde::deser::SliceWithPos<'a>,
-                        ) -> core::result::Result<Self::DeserType<'a>, epserde::deser::Error>
+                        fn _deserialize_eps_inner<'epserde_desertype>(
+                            backend: &mut epserde::deser::SliceWithPos<'epserde_desertype>,
+                        ) -> core::result::Result<Self::DeserType<'epserde_desertype>, epserde::deser::Error>
+                        {
+                            use epserde::deser::DeserializeInner;
+                            match <#tag_type>::_deserialize_full_inner(backend)? {
+                                #(
+                                    #tag_values => Ok(Self::DeserType::<'_>::#variants_names{ #variant_eps_des }),
+                                )*
+                                tag => Err(epserde::deser::Error::InvalidTag(tag as usize)),
+                            }
+                        }
+                    }
+
+                    #[automatically_derived]
+                    impl<#generics_deserialize> epserde::deser::DeserializeMutInner for #name<#generics_names> #where_clause_des_mut {
+                        type DeserMutType<'epserde_desertype> = #name<#(#deser_mut_type_generics,)*>;
+
+                        fn _deserialize_eps_mut_inner<'epserde_desertype>(
+                            backend: &mut epserde::deser::SliceWithPosMut<'epserde_desertype>,
+                        ) -> core::result::Result<Self::DeserMutType<'epserde_desertype>, epserde::deser::Error>
                         {
                             use epserde::deser::DeserializeInner;
-                            match usize::deserialize_full(backend)? {
+                            use epserde::deser::DeserializeMutInner;
+                            match <#tag_type>::_deserialize_full_inner(backend)? {
                                 #(
-                                    #tag => Ok(Self::DeserType::<'_>::#variants_names{ #variant_eps_des }),
+                                    #tag_values => Ok(Self::DeserMutType::<'_>::#variants_names{ #variant_eps_mut_des }),
                                 )*
-                                tag => Err(epserde::deser::Error::InvalidTag(tag)),
+                                tag => Err(epserde::deser::Error::InvalidTag(tag as usize)),
                             }
                         }
                     }
                 }
             }
         }
-        _ => todo!("Union types are not currently supported"),
+        Data::Union(u) => {
+            check_no_field_attrs(&syn::Fields::Named(u.fields.clone()))?;
+            if !is_zero_copy {
+                return Err(syn::Error::new_spanned(
+                    u.union_token,
+                    format!("union {} must be declared as zero copy", name),
+                ));
+            }
+
+            let fields_types = u
+                .fields
+                .named
+                .iter()
+                .map(|field| &field.ty)
+                .collect::<Vec<_>>();
+
+            let where_clause = derive_input
+                .generics
+                .where_clause
+                .clone()
+                .unwrap_or_else(|| WhereClause {
+                    where_token: token::Where::default(),
+                    predicates: Punctuated::new(),
+                });
+
+            let mut where_clause_des = where_clause.clone();
+            let mut where_clause_ser = where_clause.clone();
+
+            fields_types.iter().for_each(|ty| {
+                where_clause_ser
+                    .predicates
+                    .push(syn::parse_quote!(#ty: epserde::ser::SerializeInner));
+                where_clause_des
+                    .predicates
+                    .push(syn::parse_quote!(#ty: epserde::deser::DeserializeInner));
+            });
+            add_zero_copy_bounds(&mut where_clause_ser, &fields_types);
+            add_zero_copy_bounds(&mut where_clause_des, &fields_types);
+
+            let fields_check = zero_copy_fields_check(
+                &name,
+                &derive_input.generics,
+                &u.fields
+                    .named
+                    .iter()
+                    .map(|field| {
+                        (
+                            format!("field `{}`", field.ident.as_ref().unwrap()),
+                            &field.ty,
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            );
+
+            quote! {
+                #fields_check
+
+                #[automatically_derived]
+                impl<#generics> epserde::traits::CopyType for  #name<#generics_names> #where_clause {
+                    type Copy = epserde::traits::Zero;
+                }
+
+                #[automatically_derived]
+                impl<#generics> epserde::traits::ZeroOptionCopyType for  #name<#generics_names> #where_clause {
+                    type OptionCopy = #option_copy;
+                }
+
+                #[automatically_derived]
+                impl<#generics_serialize> epserde::ser::SerializeInner for #name<#generics_names> #where_clause_ser {
+                    // Compute whether the type could be zero copy
+                    const IS_ZERO_COPY: bool = #is_repr_c #(
+                        && <#fields_types>::IS_ZERO_COPY
+                    )*;
+
+                    // The type is declared as zero copy, so a fortiori there is no mismatch.
+                    const ZERO_COPY_MISMATCH: bool = false;
+
+                    #[inline(always)]
+                    fn _serialize_inner(&self, backend: &mut impl epserde::ser::WriteWithNames) -> epserde::ser::Result<()> {
+                        epserde::ser::helpers::serialize_zero(backend, self)
+                    }
+                }
+
+                // We cannot know which field of a union is valid, so
+                // every bit pattern is accepted.
+                #[automatically_derived]
+                impl<#generics_deserialize> epserde::deser::DeserializeInner for #name<#generics_names> #where_clause_des
+                {
+                    fn _deserialize_full_inner(
+                        backend: &mut impl epserde::deser::ReadWithPos,
+                    ) -> core::result::Result<Self, epserde::deser::Error> {
+                        epserde::deser::helpers::deserialize_full_zero::<Self>(backend)
+                    }
+
+                    type DeserType<'epserde_desertype> = &'epserde_desertype #name<#generics_names>;
+
+                    fn _deserialize_eps_inner<'epserde_desertype>(
+                        backend: &mut epserde::deser::SliceWithPos<'epserde_desertype>,
+                    ) -> core::result::Result<Self::DeserType<'epserde_desertype>, epserde::deser::Error>
+                    {
+                        epserde::deser::helpers::deserialize_eps_zero::<Self>(backend)
+                    }
+                }
+
+                #[automatically_derived]
+                impl<#generics_deserialize> epserde::deser::DeserializeMutInner for #name<#generics_names> #where_clause_des
+                {
+                    type DeserMutType<'epserde_desertype> = &'epserde_desertype mut #name<#generics_names>;
+
+                    fn _deserialize_eps_mut_inner<'epserde_desertype>(
+                        backend: &mut epserde::deser::SliceWithPosMut<'epserde_desertype>,
+                    ) -> core::result::Result<Self::DeserMutType<'epserde_desertype>, epserde::deser::Error>
+                    {
+                        epserde::deser::helpers::deserialize_eps_mut_zero::<Self>(backend)
+                    }
+                }
+            }
+        }
     };
 
-    let mut out: TokenStream = out.into();
+    let mut out = out;
     // automatically derive type hash
-    out.extend(epserde_type_hash(input_for_typehash));
-    out
+    out.extend(type_info(input_for_typehash)?);
+    Ok(out)
+}
+
+/// Return a constant whose evaluation issues a deprecation warning, pointing at
+/// the type, if all fields are zero-copy but the type has been declared neither
+/// `zero_copy` nor `deep_copy`.
+///
+/// Types with parameters are left to the runtime check, as the copy type of
+/// their fields cannot be resolved at the definition site.
+fn zero_copy_mismatch_lint(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    is_deep_copy: bool,
+    fields_types: &[impl ToTokens],
+) -> proc_macro2::TokenStream {
+    if is_deep_copy || !generics.params.is_empty() {
+        return quote!();
+    }
+    let selector = fields_types.iter().fold(quote!(epserde::traits::Zero), |acc, ty| {
+        quote!(<<#ty as epserde::traits::CopyType>::Copy as epserde::traits::CopySelector>::And<#acc>)
+    });
+    quote_spanned! {name.span()=>
+        const _: () = epserde::ser::helpers::ZeroCopyMismatch::<#selector>::check();
+    }
 }
 
-#[proc_macro_derive(TypeInfo, attributes(zero_copy, deep_copy))]
+/// Return the where clause of a type with the additional requirement that
+/// the given field types implement `ReprHash`.
+///
+/// Bounding the type parameters is not sufficient, as the implementation
+/// for some types requires further bounds (e.g., `Option<T>` requires
+/// that `T` implements `CopyType`).
+fn where_clause_repr_hash(generics: &syn::Generics, fields_types: &[syn::Type]) -> WhereClause {
+    let mut where_clause = generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| WhereClause {
+            where_token: token::Where::default(),
+            predicates: Punctuated::new(),
+        });
+    fields_types.iter().for_each(|ty| {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#ty: epserde::traits::ReprHash));
+    });
+    where_clause
+}
+
+/// Return the where clause of a zero-copy type with the additional
+/// requirement that the given field types implement `MaxSizeOf`.
+///
+/// As in [`add_zero_copy_bounds`], the bounds are higher-ranked, so that
+/// the implementation does not apply if a field is not zero-copy.
+fn where_clause_max_size_of(generics: &syn::Generics, fields_types: &[syn::Type]) -> WhereClause {
+    let mut where_clause = generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| WhereClause {
+            where_token: token::Where::default(),
+            predicates: Punctuated::new(),
+        });
+    fields_types.iter().for_each(|ty| {
+        where_clause.predicates.push(syn::parse_quote!(
+            for<'epserde_zero_copy> #ty: epserde::traits::MaxSizeOf
+        ));
+    });
+    where_clause
+}
+
+#[proc_macro_derive(TypeInfo, attributes(zero_copy, deep_copy, epserde))]
 pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
-    let (_, is_zero_copy, _) = check_attrs(&input);
+    type_info(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Generate the implementations of the derive macro `TypeInfo`.
+fn type_info(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let (_, is_zero_copy, _, _) = check_attrs(&input)?;
 
     let CommonDeriveInput {
         name,
@@ -791,37 +2509,48 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
 
     let out = match input.data {
         Data::Struct(s) => {
-            let fields_names = s
-                .fields
-                .iter()
-                .enumerate()
-                .map(|(field_idx, field)| {
-                    field
-                        .ident
-                        .as_ref()
-                        .map(|ident| ident.to_string())
-                        .unwrap_or_else(|| field_idx.to_string())
-                })
-                .collect::<Vec<_>>();
-
-            let fields_types = s
-                .fields
-                .iter()
-                .map(|field| field.ty.to_owned())
-                .collect::<Vec<_>>();
+            let mut fields_names = vec![];
+            let mut fields_types = vec![];
+            // Hashes of the names of the fields in declaration order; fields
+            // with custom serialization are hashed together with the path of
+            // the module and their type, as they might not implement TypeHash
+            let mut names_hashes = vec![];
+            // Aligned fields and their alignment
+            let mut aligned_names = vec![];
+            let mut aligns = vec![];
+
+            for (field_idx, field) in s.fields.iter().enumerate() {
+                let attrs = FieldAttrs::new(field)?;
+                let field_name = attrs.rename.unwrap_or(field_name(field_idx, field));
+                if let Some(align) = attrs.align {
+                    aligned_names.push(field_name.clone());
+                    aligns.push(align);
+                }
+                if let Some(with) = &attrs.with {
+                    let with = unqualified(with.to_token_stream());
+                    let ty = unqualified(field.ty.to_token_stream());
+                    names_hashes.push(quote! {
+                        "with".hash(hasher);
+                        #field_name.hash(hasher);
+                        #with.hash(hasher);
+                        #ty.hash(hasher);
+                    });
+                } else if !attrs.skip {
+                    names_hashes.push(quote!(#field_name.hash(hasher);));
+                    fields_names.push(field_name);
+                    fields_types.push(field.ty.to_owned());
+                }
+            }
 
             // Build type name
             let name_literal = name.to_string();
 
             // Add reprs
-            let repr = input
-                .attrs
-                .iter()
-                .filter(|x| x.meta.path().is_ident("repr"))
-                .map(|x| x.meta.require_list().unwrap().tokens.to_string())
-                .collect::<Vec<_>>();
+            let repr = repr_strings(&input.attrs)?;
 
             if is_zero_copy {
+                let where_clause_maxsizeof =
+                    where_clause_max_size_of(&input.generics, &fields_types);
                 quote! {
                     #[automatically_derived]
                     impl<#generics_typehash> epserde::traits::TypeHash for #name<#generics_names> #where_clause{
@@ -869,7 +2598,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                         }
                     }
 
-                    impl<#generics_maxsizeof> epserde::traits::MaxSizeOf for #name<#generics_names> #where_clause{
+                    impl<#generics_maxsizeof> epserde::traits::MaxSizeOf for #name<#generics_names> #where_clause_maxsizeof{
                         #[inline(always)]
                         fn max_size_of() -> usize {
                             let mut max_size_of = std::mem::align_of::<Self>();
@@ -884,6 +2613,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                     }
                 }
             } else {
+                let where_clause_reprhash = where_clause_repr_hash(&input.generics, &fields_types);
                 quote! {
                     #[automatically_derived]
                     impl<#generics_typehash> epserde::traits::TypeHash for #name<#generics_names> #where_clause{
@@ -899,7 +2629,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                             // Hash in struct and field names.
                             #name_literal.hash(hasher);
                             #(
-                                #fields_names.hash(hasher);
+                                #names_hashes
                             )*
                             // Recurse on all fields.
                             #(
@@ -908,7 +2638,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                         }
                     }
 
-                    impl<#generics_reprhash> epserde::traits::ReprHash for #name<#generics_names> #where_clause{
+                    impl<#generics_reprhash> epserde::traits::ReprHash for #name<#generics_names> #where_clause_reprhash{
                         #[inline(always)]
                         fn repr_hash(
                             hasher: &mut impl core::hash::Hasher,
@@ -921,6 +2651,12 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                                 *offset_of = 0;
                                 <#fields_types as epserde::traits::ReprHash>::repr_hash(hasher, offset_of);
                             )*
+                            // Hash in the alignment of aligned fields.
+                            #(
+                                core::hash::Hash::hash(&"align", hasher);
+                                core::hash::Hash::hash(&#aligned_names, hasher);
+                                core::hash::Hash::hash(&#aligns, hasher);
+                            )*
                         }
                     }
                 }
@@ -939,6 +2675,11 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
             let mut var_type_hashes = Vec::new();
             let mut var_repr_hashes = Vec::new();
             let mut var_max_size_ofs = Vec::new();
+            let fields_types = e
+                .variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter().map(|field| field.ty.clone()))
+                .collect::<Vec<_>>();
 
             e.variants.iter().for_each(|variant| {
                 let ident = variant.ident.to_owned();
@@ -1001,18 +2742,81 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                 var_max_size_ofs.push(var_max_size_of);
             });
 
+            // For zero-copy enums, hash in the integer representation, and the
+            // discriminant of each variant, which we obtain from a fieldless enum
+            // with the same representation and discriminants.
+            //
+            // For deep-copy enums, hash in the tag type, and the tag of each
+            // variant; variants are hashed in tag order, so that reordering
+            // variants does not change the type hash.
+            let tag_type_hash = if is_zero_copy {
+                let tag_repr = int_repr(&input.attrs)?
+                    .map(|x| x.to_token_stream())
+                    .unwrap_or(quote!(C));
+                let tag_repr_name = tag_repr.to_string();
+                let variants_names = e
+                    .variants
+                    .iter()
+                    .map(|variant| &variant.ident)
+                    .collect::<Vec<_>>();
+                let discriminants = e.variants.iter().map(|variant| {
+                    let ident = &variant.ident;
+                    match &variant.discriminant {
+                        Some((_, expr)) => quote!(#ident = #expr),
+                        None => quote!(#ident),
+                    }
+                });
+                var_type_hashes = variants_names
+                    .iter()
+                    .zip(&var_type_hashes)
+                    .map(|(ident, var_type_hash)| {
+                        quote! {
+                            (Discriminant::#ident as i128).hash(hasher);
+                            #var_type_hash
+                        }
+                    })
+                    .collect();
+                quote! {
+                    #[allow(dead_code, clippy::enum_variant_names)]
+                    #[repr(#tag_repr)]
+                    enum Discriminant {
+                        #(#discriminants,)*
+                    }
+                    "tag".hash(hasher);
+                    #tag_repr_name.hash(hasher);
+                }
+            } else {
+                let (tags, tag_type) = enum_tags(&input.attrs, &e)?;
+                let tag_type = tag_type.to_string();
+                let mut order = (0..tags.len()).collect::<Vec<_>>();
+                order.sort_by_key(|&i| tags[i]);
+                var_type_hashes = order
+                    .iter()
+                    .map(|&i| {
+                        let tag = tags[i];
+                        let var_type_hash = &var_type_hashes[i];
+                        quote! {
+                            #tag.hash(hasher);
+                            #var_type_hash
+                        }
+                    })
+                    .collect();
+                var_repr_hashes = order.iter().map(|&i| var_repr_hashes[i].clone()).collect();
+                quote! {
+                    "tag".hash(hasher);
+                    #tag_type.hash(hasher);
+                }
+            };
+
             // Build type name
             let name_literal = name.to_string();
 
             // Add reprs
-            let repr = input
-                .attrs
-                .iter()
-                .filter(|x| x.meta.path().is_ident("repr"))
-                .map(|x| x.meta.require_list().unwrap().tokens.to_string())
-                .collect::<Vec<_>>();
+            let repr = repr_strings(&input.attrs)?;
 
             if is_zero_copy {
+                let where_clause_maxsizeof =
+                    where_clause_max_size_of(&input.generics, &fields_types);
                 quote! {
                     #[automatically_derived]
                     impl<#generics_typehash> epserde::traits::TypeHash for #name<#generics_names> #where_clause{
@@ -1026,6 +2830,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                             "ZeroCopy".hash(hasher);
                             // Hash in struct and field names.
                             #name_literal.hash(hasher);
+                            #tag_type_hash
                             #(
                                 #var_type_hashes
                             )*
@@ -1055,7 +2860,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                         }
                     }
 
-                    impl<#generics_maxsizeof> epserde::traits::MaxSizeOf for #name<#generics_names> #where_clause{
+                    impl<#generics_maxsizeof> epserde::traits::MaxSizeOf for #name<#generics_names> #where_clause_maxsizeof{
                         #[inline(always)]
                         fn max_size_of() -> usize {
                             let mut max_size_of = std::mem::align_of::<Self>();
@@ -1067,6 +2872,7 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                     }
                 }
             } else {
+                let where_clause_reprhash = where_clause_repr_hash(&input.generics, &fields_types);
                 quote! {
                     #[automatically_derived]
                     impl<#generics_typehash> epserde::traits::TypeHash for #name<#generics_names> #where_clause{
@@ -1081,13 +2887,14 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                             "DeepCopy".hash(hasher);
                             // Hash in struct and field names.
                             #name_literal.hash(hasher);
+                            #tag_type_hash
                             #(
                                 #var_type_hashes
                             )*
                         }
                     }
 
-                    impl<#generics_reprhash> epserde::traits::ReprHash for #name<#generics_names> #where_clause{
+                    impl<#generics_reprhash> epserde::traits::ReprHash for #name<#generics_names> #where_clause_reprhash{
                         #[inline(always)]
                         fn repr_hash(
                             hasher: &mut impl core::hash::Hasher,
@@ -1105,7 +2912,91 @@ pub fn epserde_type_hash(input: TokenStream) -> TokenStream {
                 }
             }
         }
-        _ => todo!("Union types are not currently supported"),
+        Data::Union(u) => {
+            let fields_names = u
+                .fields
+                .named
+                .iter()
+                .map(|field| field.ident.as_ref().unwrap().to_string())
+                .collect::<Vec<_>>();
+
+            let fields_types = u
+                .fields
+                .named
+                .iter()
+                .map(|field| field.ty.to_owned())
+                .collect::<Vec<_>>();
+
+            let name_literal = name.to_string();
+
+            let repr = repr_strings(&input.attrs)?;
+            let where_clause_maxsizeof = where_clause_max_size_of(&input.generics, &fields_types);
+
+            quote! {
+                #[automatically_derived]
+                impl<#generics_typehash> epserde::traits::TypeHash for #name<#generics_names> #where_clause{
+
+                    #[inline(always)]
+                    fn type_hash(
+                        hasher: &mut impl core::hash::Hasher,
+                    ) {
+                        use core::hash::Hash;
+                        // Hash in ZeroCopy
+                        "ZeroCopy".hash(hasher);
+                        // Hash in union and field names.
+                        "union".hash(hasher);
+                        #name_literal.hash(hasher);
+                        #(
+                            #fields_names.hash(hasher);
+                        )*
+                        // Recurse on all fields.
+                        #(
+                            <#fields_types as epserde::traits::TypeHash>::type_hash(hasher);
+                        )*
+                    }
+                }
+
+                impl<#generics_reprhash> epserde::traits::ReprHash for #name<#generics_names> #where_clause{
+                    #[inline(always)]
+                    fn repr_hash(
+                        hasher: &mut impl core::hash::Hasher,
+                        offset_of: &mut usize,
+                    ) {
+                        use core::hash::Hash;
+                        core::mem::size_of::<Self>().hash(hasher);
+                        // Hash in representation data.
+                        #(
+                            #repr.hash(hasher);
+                        )*
+                        // Recurse on all fields, which all start at the
+                        // offset of the union.
+                        let union_offset_of = *offset_of;
+                        #(
+                            *offset_of = union_offset_of;
+                            <#fields_types as epserde::traits::ReprHash>::repr_hash(
+                                hasher,
+                                offset_of,
+                            );
+                        )*
+                        *offset_of = union_offset_of + core::mem::size_of::<Self>();
+                    }
+                }
+
+                impl<#generics_maxsizeof> epserde::traits::MaxSizeOf for #name<#generics_names> #where_clause_maxsizeof{
+                    #[inline(always)]
+                    fn max_size_of() -> usize {
+                        let mut max_size_of = std::mem::align_of::<Self>();
+                        // Recurse on all fields.
+                        #(
+                            if max_size_of < <#fields_types as epserde::traits::MaxSizeOf>::max_size_of() {
+                                max_size_of = <#fields_types as epserde::traits::MaxSizeOf>::max_size_of();
+                            }
+                        )*
+                        max_size_of
+                    }
+                }
+            }
+        }
     };
-    out.into()
+    Ok(out)
 }
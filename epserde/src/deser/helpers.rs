@@ -11,24 +11,58 @@ Helpers for deserialization.
 
 */
 
-use super::SliceWithPos;
-use super::{read::*, DeserializeInner};
+use super::{read::*, DeserializeInner, DeserializeMutInner};
+use super::{SliceWithPos, SliceWithPosMut, StrPool, StrPoolMut};
 use crate::deser;
 use crate::traits::*;
 use core::mem::MaybeUninit;
 
+/// Check that `bytes` contains a sequence of valid values of a zero-copy type.
+///
+/// Bytes are scanned only if [`DeserializeInner::MUST_CHECK_ZERO_COPY`] is true.
+#[inline(always)]
+pub fn check_slice_zero<T: ZeroCopy + DeserializeInner>(bytes: &[u8]) -> deser::Result<()> {
+    let size = core::mem::size_of::<T>();
+    if T::MUST_CHECK_ZERO_COPY && size != 0 {
+        for chunk in bytes.chunks_exact(size) {
+            T::_check_zero_copy(chunk)?;
+        }
+    }
+    Ok(())
+}
+
+/// Return the value of the discriminant of a zero-copy enum, given the
+/// bytes representing it in native byte order.
+///
+/// This function is used by the derive code to report invalid discriminants;
+/// discriminants longer than a `usize` are truncated.
+pub fn zero_copy_tag(bytes: &[u8]) -> usize {
+    let mut tag = [0; core::mem::size_of::<usize>()];
+    let len = bytes.len().min(tag.len());
+    if cfg!(target_endian = "little") {
+        tag[..len].copy_from_slice(&bytes[..len]);
+        usize::from_le_bytes(tag)
+    } else {
+        tag[core::mem::size_of::<usize>() - len..].copy_from_slice(&bytes[bytes.len() - len..]);
+        usize::from_be_bytes(tag)
+    }
+}
+
 /// Full-copy deserialize a zero-copy structure.
-pub fn deserialize_full_zero<T: ZeroCopy>(backend: &mut impl ReadWithPos) -> deser::Result<T> {
+pub fn deserialize_full_zero<T: ZeroCopy + DeserializeInner>(
+    backend: &mut impl ReadWithPos,
+) -> deser::Result<T> {
     backend.align::<T>()?;
+    let mut buf = MaybeUninit::<T>::uninit();
+    // SAFETY: we read exactly size_of::<T>() bytes into the buffer,
+    // and we check that they represent a valid value before assuming
+    // initialization.
     unsafe {
-        #[allow(clippy::uninit_assumed_init)]
-        let mut buf: T = MaybeUninit::uninit().assume_init();
-        let slice = core::slice::from_raw_parts_mut(
-            &mut buf as *mut T as *mut u8,
-            core::mem::size_of::<T>(),
-        );
+        let slice =
+            core::slice::from_raw_parts_mut(buf.as_mut_ptr() as *mut u8, core::mem::size_of::<T>());
         backend.read_exact(slice)?;
-        Ok(buf)
+        T::_check_zero_copy(slice)?;
+        Ok(buf.assume_init())
     }
 }
 
@@ -41,13 +75,18 @@ pub fn deserialize_full_vec_zero<T: DeserializeInner + ZeroCopy>(
 ) -> deser::Result<Vec<T>> {
     let len = usize::_deserialize_full_inner(backend)?;
     backend.align::<T>()?;
-    let mut res = Vec::with_capacity(len);
-    // SAFETY: we just allocated this vector so it is safe to set the length.
-    // read_exact guarantees that the vector will be filled with data.
-    #[allow(clippy::uninit_vec)]
+    let mut res = Vec::<T>::with_capacity(len);
+    // SAFETY: read_exact guarantees that the spare capacity of the vector
+    // will be filled with data, and we check that the data represents
+    // valid values before setting the length.
     unsafe {
+        let bytes = core::slice::from_raw_parts_mut(
+            res.as_mut_ptr() as *mut u8,
+            len * core::mem::size_of::<T>(),
+        );
+        backend.read_exact(bytes)?;
+        check_slice_zero::<T>(bytes)?;
         res.set_len(len);
-        backend.read_exact(res.align_to_mut::<u8>().1)?;
     }
 
     Ok(res)
@@ -67,11 +106,12 @@ pub fn deserialize_full_vec_deep<T: DeserializeInner + DeepCopy>(
 
 /// ε-copy deserialize a reference to a zero-copy structure
 /// backed by the `data` field of `backend`.
-pub fn deserialize_eps_zero<'a, T: ZeroCopy>(
+pub fn deserialize_eps_zero<'a, T: ZeroCopy + DeserializeInner>(
     backend: &mut SliceWithPos<'a>,
 ) -> deser::Result<&'a T> {
     let bytes = core::mem::size_of::<T>();
     backend.align::<T>()?;
+    T::_check_zero_copy(&backend.data[..bytes])?;
     let (pre, data, after) = unsafe { backend.data[..bytes].align_to::<T>() };
     debug_assert!(pre.is_empty());
     debug_assert!(after.is_empty());
@@ -82,12 +122,13 @@ pub fn deserialize_eps_zero<'a, T: ZeroCopy>(
 
 /// ε-copy deserialize a reference to a slice of zero-copy structures
 /// backed by the `data` field of `backend`.
-pub fn deserialize_eps_slice_zero<'a, T: ZeroCopy>(
+pub fn deserialize_eps_slice_zero<'a, T: ZeroCopy + DeserializeInner>(
     backend: &mut SliceWithPos<'a>,
 ) -> deser::Result<&'a [T]> {
     let len = usize::_deserialize_full_inner(backend)?;
     let bytes = len * core::mem::size_of::<T>();
     backend.align::<T>()?;
+    check_slice_zero::<T>(&backend.data[..bytes])?;
     let (pre, data, after) = unsafe { backend.data[..bytes].align_to::<T>() };
     debug_assert!(pre.is_empty());
     debug_assert!(after.is_empty());
@@ -106,3 +147,130 @@ pub fn deserialize_eps_vec_deep<'a, T: DeepCopy + DeserializeInner>(
     }
     Ok(res)
 }
+
+/// Mutable ε-copy deserialize a reference to a zero-copy structure
+/// backed by the `data` field of `backend`.
+pub fn deserialize_eps_mut_zero<'a, T: ZeroCopy + DeserializeInner>(
+    backend: &mut SliceWithPosMut<'a>,
+) -> deser::Result<&'a mut T> {
+    let bytes = core::mem::size_of::<T>();
+    backend.align::<T>()?;
+    T::_check_zero_copy(&backend.data[..bytes])?;
+    let (pre, data, after) = unsafe { backend.split(bytes).align_to_mut::<T>() };
+    debug_assert!(pre.is_empty());
+    debug_assert!(after.is_empty());
+    Ok(&mut data[0])
+}
+
+/// Mutable ε-copy deserialize a reference to a slice of zero-copy structures
+/// backed by the `data` field of `backend`.
+pub fn deserialize_eps_mut_slice_zero<'a, T: ZeroCopy + DeserializeInner>(
+    backend: &mut SliceWithPosMut<'a>,
+) -> deser::Result<&'a mut [T]> {
+    let len = usize::_deserialize_full_inner(backend)?;
+    let bytes = len * core::mem::size_of::<T>();
+    backend.align::<T>()?;
+    check_slice_zero::<T>(&backend.data[..bytes])?;
+    let (pre, data, after) = unsafe { backend.split(bytes).align_to_mut::<T>() };
+    debug_assert!(pre.is_empty());
+    debug_assert!(after.is_empty());
+    Ok(data)
+}
+
+/// Mutable ε-copy deserialize a vector of deep-copy structures.
+pub fn deserialize_eps_mut_vec_deep<'a, T: DeepCopy + DeserializeMutInner>(
+    backend: &mut SliceWithPosMut<'a>,
+) -> deser::Result<Vec<<T as DeserializeMutInner>::DeserMutType<'a>>> {
+    let len = usize::_deserialize_full_inner(backend)?;
+    let mut res = Vec::with_capacity(len);
+    for _ in 0..len {
+        res.push(T::_deserialize_eps_mut_inner(backend)?);
+    }
+    Ok(res)
+}
+
+/// Full-copy deserialize a vector of [atomic](AtomicCopy) structures,
+/// serialized as a vector of the non-atomic type.
+pub fn deserialize_full_vec_atomic<T: AtomicCopy>(
+    backend: &mut impl ReadWithPos,
+) -> deser::Result<Vec<T>>
+where
+    T::NonAtomic: DeserializeInner,
+{
+    Ok(deserialize_full_vec_zero::<T::NonAtomic>(backend)?
+        .into_iter()
+        .map(T::new)
+        .collect())
+}
+
+/// Mutable ε-copy deserialize a reference to a slice of [atomic](AtomicCopy)
+/// structures, serialized as a vector of the non-atomic type, backed by the
+/// `data` field of `backend`.
+pub fn deserialize_eps_mut_slice_atomic<'a, T: AtomicCopy>(
+    backend: &mut SliceWithPosMut<'a>,
+) -> deser::Result<&'a mut [T]>
+where
+    T::NonAtomic: DeserializeInner,
+{
+    let data = deserialize_eps_mut_slice_zero::<T::NonAtomic>(backend)?;
+    debug_assert_eq!(
+        core::mem::size_of::<T>(),
+        core::mem::size_of::<T::NonAtomic>()
+    );
+    debug_assert_eq!(data.as_ptr() as usize % core::mem::align_of::<T>(), 0);
+    // SAFETY: by the safety contract of AtomicCopy, T has the same representation
+    // of T::NonAtomic, and alignment not larger than its size, which is the
+    // alignment enforced by deserialize_eps_mut_slice_zero.
+    Ok(unsafe { core::slice::from_raw_parts_mut(data.as_mut_ptr() as *mut T, data.len()) })
+}
+
+/// Full-copy deserialize a vector of strings serialized as a [string
+/// pool](super::StrPool).
+pub fn deserialize_full_vec_pool<T: for<'b> From<&'b str>>(
+    backend: &mut impl ReadWithPos,
+) -> deser::Result<Vec<T>> {
+    let offsets = deserialize_full_vec_zero::<usize>(backend)?;
+    let data = deserialize_full_vec_zero::<u8>(backend)?;
+    super::str_pool::check_pool(&offsets, &data)?;
+    // SAFETY: the pool has been validated by check_pool.
+    let data = unsafe { core::str::from_utf8_unchecked(&data) };
+    Ok(offsets
+        .windows(2)
+        .map(|w| T::from(&data[w[0]..w[1]]))
+        .collect())
+}
+
+/// ε-copy deserialize a [string pool](super::StrPool) backed by the `data`
+/// field of `backend`.
+pub fn deserialize_eps_pool<'a>(backend: &mut SliceWithPos<'a>) -> deser::Result<StrPool<'a>> {
+    let offsets = deserialize_eps_slice_zero::<usize>(backend)?;
+    let data = deserialize_eps_slice_zero::<u8>(backend)?;
+    StrPool::new(offsets, data)
+}
+
+/// Mutable ε-copy deserialize a [string pool](super::StrPoolMut) backed by the
+/// `data` field of `backend`.
+pub fn deserialize_eps_mut_pool<'a>(
+    backend: &mut SliceWithPosMut<'a>,
+) -> deser::Result<StrPoolMut<'a>> {
+    let offsets = deserialize_eps_mut_slice_zero::<usize>(backend)?;
+    let data = deserialize_eps_mut_slice_zero::<u8>(backend)?;
+    StrPoolMut::new(offsets, data)
+}
+
+/// Run `f` on the backend after raising its [minimum alignment](super::DeserializeOptions::min_align)
+/// to at least `min_align`, restoring the previous minimum alignment afterwards.
+///
+/// This function is the deserialization counterpart of
+/// [`ser::helpers::with_min_align`](crate::ser::helpers::with_min_align).
+pub fn with_min_align<B: ReadWithPos, R>(
+    backend: &mut B,
+    min_align: usize,
+    f: impl FnOnce(&mut B) -> deser::Result<R>,
+) -> deser::Result<R> {
+    let prev = backend.options().min_align;
+    backend.set_min_align(prev.max(min_align))?;
+    let result = f(backend);
+    backend.set_min_align(prev)?;
+    result
+}
@@ -5,10 +5,12 @@
  */
 
 use bitflags::bitflags;
-use core::ops::Deref;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+use std::alloc::{Layout, LayoutError};
 
 bitflags! {
-    /// Flags for [`map`] and [`load_mmap`].
+    /// Flags for [`map`], [`map_mut`], and [`load_mmap`].
     #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
     pub struct Flags: u32 {
         /// Suggest to map a region using transparent huge pages. This flag
@@ -38,6 +40,77 @@ impl Flags {
             false => mmap_rs::MmapFlags::empty(),
         }
     }
+
+    /// Translates internal flags to `mmap_rs` flags for a shared, writable
+    /// mapping.
+    ///
+    /// Differently from [`Flags::mmap_flags`], we never pass `COPY_ON_WRITE`,
+    /// as changes must be written back to the file.
+    pub(crate) fn mmap_flags_shared(&self) -> mmap_rs::MmapFlags {
+        match self.contains(Flags::TRANSPARENT_HUGE_PAGES) {
+            true => mmap_rs::MmapFlags::TRANSPARENT_HUGE_PAGES,
+            false => mmap_rs::MmapFlags::empty(),
+        }
+    }
+}
+
+/// A heap-allocated, zero-initialized memory region with a given alignment.
+///
+/// Differently from a `Vec<u8>`, which would be deallocated with alignment
+/// one, the memory is deallocated with the same layout used to allocate it.
+pub struct AlignedBuffer {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+// SAFETY: the buffer owns its memory, as a `Vec<u8>` would.
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
+
+impl AlignedBuffer {
+    /// Allocate a zero-initialized region of `len` bytes aligned to `align` bytes.
+    ///
+    /// An error is returned if `align` is not a power of two or if `len`,
+    /// rounded up to a multiple of `align`, overflows an `isize`.
+    pub fn zeroed(len: usize, align: usize) -> Result<Self, LayoutError> {
+        let layout = Layout::from_size_align(len, align)?;
+        let ptr = if len == 0 {
+            // Zero-size allocations are not allowed, so we use a dangling,
+            // suitably aligned pointer, which is never deallocated
+            NonNull::new(core::ptr::without_provenance_mut(align)).unwrap()
+        } else {
+            // SAFETY: the layout has nonzero size
+            let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
+            NonNull::new(ptr).unwrap_or_else(|| std::alloc::handle_alloc_error(layout))
+        };
+        Ok(Self { ptr, layout })
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            // SAFETY: the memory was allocated in `zeroed` with this layout
+            unsafe { std::alloc::dealloc(self.ptr.as_ptr(), self.layout) }
+        }
+    }
+}
+
+impl Deref for AlignedBuffer {
+    type Target = [u8];
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the memory is initialized and owned by the buffer
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.layout.size()) }
+    }
+}
+
+impl DerefMut for AlignedBuffer {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the memory is initialized and owned by the buffer
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.layout.size()) }
+    }
 }
 
 /// Possible backends of a [`MemCase`]. The `None` variant is used when the data structure is
@@ -49,12 +122,17 @@ pub enum MemBackend {
     /// No backend. The data structure is a standard Rust data structure.
     /// This variant is returned by [`MemCase::encase`].
     None,
-    /// The backend is a heap-allocated in a memory region aligned to 4096 bytes.
+    /// The backend is a heap-allocated memory region aligned to
+    /// [`LOAD_MEM_ALIGN`](crate::deser::LOAD_MEM_ALIGN) bytes.
     /// This variant is returned by [`crate::deser::Deserialize::load_mem`].
-    Memory(Vec<u8>),
+    Memory(AlignedBuffer),
     /// The backend is the result to a call to `mmap()`.
     /// This variant is returned by [`crate::deser::Deserialize::load_mmap`] and [`crate::deser::Deserialize::mmap`].
     Mmap(mmap_rs::Mmap),
+    /// The backend is the result to a call to `mmap()` with write permissions
+    /// on a shared mapping.
+    /// This variant is returned by [`crate::deser::DeserializeMut::mmap_mut`].
+    MmapMut(mmap_rs::MmapMut),
 }
 
 impl MemBackend {
@@ -63,6 +141,16 @@ impl MemBackend {
             MemBackend::None => None,
             MemBackend::Memory(mem) => Some(mem),
             MemBackend::Mmap(mmap) => Some(mmap),
+            MemBackend::MmapMut(mmap) => Some(mmap),
+        }
+    }
+
+    pub fn as_mut(&mut self) -> Option<&mut [u8]> {
+        match self {
+            MemBackend::None => None,
+            MemBackend::Memory(mem) => Some(mem),
+            MemBackend::Mmap(_) => None,
+            MemBackend::MmapMut(mmap) => Some(mmap),
         }
     }
 }
@@ -81,7 +169,6 @@ impl MemBackend {
 /// wrapped type, using the no-op [`None`](`MemBackend#variant.None`) variant
 /// of [`MemBackend`], so a structure can be [encased](MemCase::encase)
 /// almost transparently.
-
 pub struct MemCase<S>(pub(crate) S, pub(crate) MemBackend);
 
 impl<S> MemCase<S> {
@@ -114,3 +201,59 @@ impl<S: Send + Sync> From<S> for MemCase<S> {
         MemCase::encase(s)
     }
 }
+
+/// A wrapper keeping together a mutable structure and the memory
+/// it was deserialized from.
+///
+/// [`MemCaseMut`] is the mutable counterpart of [`MemCase`], and it is returned
+/// by [`crate::deser::DeserializeMut::mmap_mut`]. It implements [`Deref`] and
+/// [`DerefMut`] to the wrapped type.
+pub struct MemCaseMut<S>(pub(crate) S, pub(crate) MemBackend);
+
+impl<S> MemCaseMut<S> {
+    /// Encases a data structure in a [`MemCaseMut`] with no backend.
+    pub fn encase(s: S) -> MemCaseMut<S> {
+        MemCaseMut(s, MemBackend::None)
+    }
+
+    /// Synchronously write to the underlying file the changes made so far,
+    /// if the backend is a memory mapping.
+    pub fn flush(&self) -> Result<(), mmap_rs::Error> {
+        match &self.1 {
+            MemBackend::MmapMut(mmap) => mmap.flush(0..mmap.size()),
+            _ => Ok(()),
+        }
+    }
+}
+
+unsafe impl<S: Send> Send for MemCaseMut<S> {}
+unsafe impl<S: Sync> Sync for MemCaseMut<S> {}
+
+impl<S> Deref for MemCaseMut<S> {
+    type Target = S;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S> DerefMut for MemCaseMut<S> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<S> AsRef<S> for MemCaseMut<S> {
+    #[inline(always)]
+    fn as_ref(&self) -> &S {
+        &self.0
+    }
+}
+
+impl<S> AsMut<S> for MemCaseMut<S> {
+    #[inline(always)]
+    fn as_mut(&mut self) -> &mut S {
+        &mut self.0
+    }
+}
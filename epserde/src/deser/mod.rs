@@ -15,27 +15,43 @@ which implement ε-copy and full-copy deserialization, respectively.
 The implementation of this trait is based on [`DeserializeInner`],
 which is automatically derived with `#[derive(Deserialize)]`.
 
+[`DeserializeMut`] provides in the same way mutable ε-copy deserialization
+through [`DeserializeMut::deserialize_eps_mut`] and [`DeserializeMut::mmap_mut`],
+and it is based on [`DeserializeMutInner`].
+
 */
 
 use crate::traits::*;
 use crate::{MAGIC, MAGIC_REV, VERSION};
 use core::ptr::addr_of_mut;
 use core::{hash::Hasher, mem::MaybeUninit};
-use std::{io::BufReader, path::Path};
+use std::{fs::OpenOptions, io::BufReader, path::Path};
 
 pub mod helpers;
 pub use helpers::*;
 pub mod mem_case;
 pub use mem_case::*;
+pub mod options;
+pub use options::*;
 pub mod read;
 pub use read::*;
 pub mod reader_with_pos;
 pub use reader_with_pos::*;
+pub mod set_view;
+pub use set_view::*;
 pub mod slice_with_pos;
 pub use slice_with_pos::*;
+pub mod slice_with_pos_mut;
+pub use slice_with_pos_mut::*;
+pub mod str_pool;
+pub use str_pool::*;
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// The alignment of the memory allocated by [`Deserialize::load_mem`], which
+/// is the maximum alignment of zero-copy data it supports.
+pub const LOAD_MEM_ALIGN: usize = 4096;
+
 /// Main deserialization trait. It is separated from [`DeserializeInner`] to
 /// avoid that the user modify its behavior, and hide internal serialization
 /// methods.
@@ -45,9 +61,53 @@ pub type Result<T> = core::result::Result<T, Error>;
 /// [`Deserialize::load_full`], [`Deserialize::load_mem`], and [`Deserialize::mmap`].
 pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
     /// Fully deserialize a structure of this type from the given backend.
-    fn deserialize_full(backend: &mut impl ReadNoStd) -> Result<Self>;
+    fn deserialize_full(backend: &mut impl ReadNoStd) -> Result<Self> {
+        Self::deserialize_full_with(&DeserializeOptions::DEFAULT, backend)
+    }
     /// ε-copy deserialize a structure of this type from the given backend.
-    fn deserialize_eps(backend: &'_ [u8]) -> Result<Self::DeserType<'_>>;
+    fn deserialize_eps(backend: &'_ [u8]) -> Result<Self::DeserType<'_>> {
+        Self::deserialize_eps_with(&DeserializeOptions::DEFAULT, backend)
+    }
+
+    /// Fully deserialize a structure of this type from the given backend
+    /// using the given [options](DeserializeOptions).
+    fn deserialize_full_with(
+        options: &DeserializeOptions,
+        backend: &mut impl ReadNoStd,
+    ) -> Result<Self>;
+    /// ε-copy deserialize a structure of this type from the given backend
+    /// using the given [options](DeserializeOptions).
+    fn deserialize_eps_with<'a>(
+        options: &DeserializeOptions,
+        backend: &'a [u8],
+    ) -> Result<Self::DeserType<'a>>;
+
+    /// Fully deserialize a structure of this type serialized by
+    /// [`serialize_raw`](crate::ser::Serialize::serialize_raw) with the
+    /// same base offset.
+    fn deserialize_full_raw(backend: &mut impl ReadNoStd, base_offset: usize) -> Result<Self> {
+        Self::deserialize_full_with(
+            &DeserializeOptions::new()
+                .header(false)
+                .base_offset(base_offset),
+            backend,
+        )
+    }
+
+    /// ε-copy deserialize a structure of this type serialized by
+    /// [`serialize_raw`](crate::ser::Serialize::serialize_raw) with the
+    /// same base offset.
+    ///
+    /// The backend must start at `base_offset` bytes from the start of the
+    /// enclosing container, which must be suitably aligned in memory.
+    fn deserialize_eps_raw(backend: &'_ [u8], base_offset: usize) -> Result<Self::DeserType<'_>> {
+        Self::deserialize_eps_with(
+            &DeserializeOptions::new()
+                .header(false)
+                .base_offset(base_offset),
+            backend,
+        )
+    }
 
     /// Commodity method to fully deserialize from a file.
     fn load_full(path: impl AsRef<Path>) -> Result<Self> {
@@ -59,6 +119,10 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
     /// Load a file into heap-allocated memory and ε-deserialize a data structure from it,
     /// returning a [`MemCase`] containing the data structure and the
     /// memory. Excess bytes are zeroed out.
+    ///
+    /// The memory is aligned to [`LOAD_MEM_ALIGN`] bytes, so zero-copy data
+    /// requiring a larger alignment (e.g., because of the field attribute
+    /// `#[epserde(align = N)]`) causes an [`AlignmentError`](Error::AlignmentError).
     fn load_mem<'a>(
         path: impl AsRef<Path>,
     ) -> anyhow::Result<MemCase<<Self as DeserializeInner>::DeserType<'a>>> {
@@ -71,20 +135,10 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
             MaybeUninit::uninit();
         let ptr = uninit.as_mut_ptr();
 
-        // SAFETY: the entire vector will be filled with data read from the file,
-        // or with zeroes if the file is shorter than the vector.
-        let mut bytes = unsafe {
-            Vec::from_raw_parts(
-                std::alloc::alloc(std::alloc::Layout::from_size_align(len, 16)?),
-                len,
-                len,
-            )
-        };
-
-        file.read_exact(&mut bytes[..file_len])?;
-        // Fixes the last few bytes to guarantee zero-extension semantics
+        // The memory is zeroed, which guarantees zero-extension semantics
         // for bit vectors and full-vector initialization.
-        bytes[file_len..].fill(0);
+        let mut bytes = AlignedBuffer::zeroed(len, LOAD_MEM_ALIGN)?;
+        file.read_exact(&mut bytes[..file_len])?;
         let backend = MemBackend::Memory(bytes);
 
         // store the backend inside the MemCase
@@ -115,7 +169,7 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
     ) -> anyhow::Result<MemCase<<Self as DeserializeInner>::DeserType<'a>>> {
         let file_len = path.as_ref().metadata()?.len() as usize;
         let mut file = std::fs::File::open(path)?;
-        let capacity = (file_len + 7) / 8;
+        let capacity = file_len.div_ceil(8);
 
         let mut uninit: MaybeUninit<MemCase<<Self as DeserializeInner>::DeserType<'_>>> =
             MaybeUninit::uninit();
@@ -198,30 +252,210 @@ pub trait Deserialize: TypeHash + ReprHash + DeserializeInner {
 ///
 /// The user should not implement this trait directly, but rather derive it.
 pub trait DeserializeInner: Sized {
+    /// Whether some bit patterns are not valid values of this type,
+    /// and thus [`DeserializeInner::_check_zero_copy`] must be called
+    /// before turning a sequence of bytes into a reference to a zero-copy
+    /// instance of this type.
+    ///
+    /// This constant is relevant only for zero-copy types, and it
+    /// makes it possible to avoid scanning slices of types in which every bit
+    /// pattern is valid (e.g., integers).
+    const MUST_CHECK_ZERO_COPY: bool = false;
+
+    /// Whether [`DeserializeInner::_check_zero_copy`] must be called
+    /// on zero-copy instances of `Option<Self>`, which are valid if they are made
+    /// of zero bytes (i.e., `None`) or if they are valid instances of `Self`.
+    ///
+    /// This constant is relevant only if
+    /// [`OptionCopyType::OptionCopy`](crate::traits::OptionCopyType::OptionCopy) is
+    /// [`Zero`](crate::traits::Zero), and it should be set to false for
+    /// types whose only invalid bit pattern is made of zero bytes (e.g.,
+    /// [non-zero integer types](core::num::NonZero)).
+    const MUST_CHECK_ZERO_COPY_OPTION: bool = Self::MUST_CHECK_ZERO_COPY;
+
     type DeserType<'a>;
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> Result<Self>;
 
     fn _deserialize_eps_inner<'a>(backend: &mut SliceWithPos<'a>) -> Result<Self::DeserType<'a>>;
+
+    /// Check that `bytes`, which has the size of `Self`, contains a valid
+    /// value of this zero-copy type.
+    ///
+    /// The default implementation accepts every bit pattern.
+    #[inline(always)]
+    fn _check_zero_copy(_bytes: &[u8]) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Blanket implementation that prevents the user from overwriting the
 /// methods in [`Deserialize`].
 ///
 /// This implementation [checks the header](`check_header`) written
-/// by the blanket implementation of [`crate::ser::Serialize`] and then delegates to
+/// by the blanket implementation of [`crate::ser::Serialize`], unless
+/// [disabled](DeserializeOptions::header), and then delegates to
 /// [`DeserializeInner::_deserialize_full_inner`] or
-/// [`DeserializeInner::_deserialize_eps_inner`].
+/// [`DeserializeInner::_deserialize_eps_inner`]. Finally, it
+/// [verifies the checksum](DeserializeOptions::checksum), if required.
 impl<T: TypeHash + ReprHash + DeserializeInner> Deserialize for T {
-    fn deserialize_full(backend: &mut impl ReadNoStd) -> Result<Self> {
-        let mut backend = ReaderWithPos::new(backend);
-        check_header::<Self>(&mut backend)?;
-        Self::_deserialize_full_inner(&mut backend)
+    fn deserialize_full_with(
+        options: &DeserializeOptions,
+        backend: &mut impl ReadNoStd,
+    ) -> Result<Self> {
+        if options.checksum {
+            let mut checksum_reader = ChecksumReader::new(backend);
+            let mut reader_with_pos = ReaderWithPos::with_options(&mut checksum_reader, *options);
+            if options.header {
+                check_header::<Self>(&mut reader_with_pos)?;
+            }
+            let value = Self::_deserialize_full_inner(&mut reader_with_pos)?;
+            let checksum = checksum_reader.checksum();
+            let mut expected = [0; core::mem::size_of::<u64>()];
+            backend.read_exact(&mut expected)?;
+            check_checksum(u64::from_ne_bytes(expected), checksum)?;
+            Ok(value)
+        } else {
+            let mut reader_with_pos = ReaderWithPos::with_options(backend, *options);
+            if options.header {
+                check_header::<Self>(&mut reader_with_pos)?;
+            }
+            Self::_deserialize_full_inner(&mut reader_with_pos)
+        }
     }
 
-    fn deserialize_eps(backend: &'_ [u8]) -> Result<Self::DeserType<'_>> {
-        let mut backend = SliceWithPos::new(backend);
-        check_header::<Self>(&mut backend)?;
-        Self::_deserialize_eps_inner(&mut backend)
+    fn deserialize_eps_with<'a>(
+        options: &DeserializeOptions,
+        backend: &'a [u8],
+    ) -> Result<Self::DeserType<'a>> {
+        let mut slice_with_pos = SliceWithPos::with_options(backend, *options);
+        if options.header {
+            check_header::<Self>(&mut slice_with_pos)?;
+        }
+        let value = Self::_deserialize_eps_inner(&mut slice_with_pos)?;
+        if options.checksum {
+            let len = slice_with_pos.pos - options.base_offset;
+            let expected = u64::_deserialize_full_inner(&mut slice_with_pos)?;
+            check_checksum(expected, xxhash_rust::xxh3::xxh3_64(&backend[..len]))?;
+        }
+        Ok(value)
+    }
+}
+
+/// Compare the checksum following the serialized data with the one computed
+/// on the data.
+fn check_checksum(expected: u64, got: u64) -> Result<()> {
+    if expected != got {
+        Err(Error::ChecksumMismatch { expected, got })
+    } else {
+        Ok(())
+    }
+}
+
+/// Mutable ε-copy deserialization trait. It is separated from [`DeserializeMutInner`]
+/// for the same reasons [`Deserialize`] is separated from [`DeserializeInner`].
+///
+/// The associated deserialization type [`DeserializeMutInner::DeserMutType`]
+/// contains mutable references to zero-copy data (e.g., `&mut [T]` for a vector
+/// of zero-copy elements), so changes are written directly into the backend.
+/// In particular, using [`DeserializeMut::mmap_mut`] changes will be persisted
+/// to the underlying file without any re-serialization.
+///
+/// Note that fields whose type is not a type parameter are fully deserialized
+/// also in this case. To avoid losing silently mutations, derived structures
+/// implement this trait only if the mutable deserialization type of such fields
+/// is the type itself (e.g., primitive types): otherwise, you can use
+/// `#[epserde(deser_struct)]` to ε-copy all fields.
+pub trait DeserializeMut: Deserialize + DeserializeMutInner {
+    /// Mutable ε-copy deserialize a structure of this type from the given backend.
+    fn deserialize_eps_mut(backend: &'_ mut [u8]) -> Result<Self::DeserMutType<'_>> {
+        Self::deserialize_eps_mut_with(&DeserializeOptions::DEFAULT, backend)
+    }
+
+    /// Mutable ε-copy deserialize a structure of this type from the given backend
+    /// using the given [options](DeserializeOptions).
+    fn deserialize_eps_mut_with<'a>(
+        options: &DeserializeOptions,
+        backend: &'a mut [u8],
+    ) -> Result<Self::DeserMutType<'a>>;
+
+    /// Memory map a file in read/write shared mode and mutable ε-deserialize a data
+    /// structure from it, returning a [`MemCaseMut`] containing the data structure
+    /// and the memory mapping.
+    ///
+    /// Changes to the data structure will be written to the file. Use
+    /// [`MemCaseMut::flush`] to write them synchronously.
+    ///
+    /// The behavior of `mmap()` can be modified by passing some [`Flags`]; otherwise,
+    /// just pass `Flags::empty()`.
+    fn mmap_mut<'a>(
+        path: impl AsRef<Path>,
+        flags: Flags,
+    ) -> anyhow::Result<MemCaseMut<<Self as DeserializeMutInner>::DeserMutType<'a>>> {
+        let file_len = path.as_ref().metadata()?.len();
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+
+        let mut uninit: MaybeUninit<MemCaseMut<<Self as DeserializeMutInner>::DeserMutType<'_>>> =
+            MaybeUninit::uninit();
+        let ptr = uninit.as_mut_ptr();
+
+        let mmap = unsafe {
+            mmap_rs::MmapOptions::new(file_len as _)?
+                .with_flags(flags.mmap_flags_shared())
+                .with_file(file, 0)
+                .map_mut()?
+        };
+
+        // store the backend inside the MemCaseMut
+        unsafe {
+            addr_of_mut!((*ptr).1).write(MemBackend::MmapMut(mmap));
+        }
+
+        let mmap = unsafe { (*ptr).1.as_mut().unwrap() };
+        // deserialize the data structure
+        let s = Self::deserialize_eps_mut(mmap)?;
+        // write the deserialized struct in the MemCaseMut
+        unsafe {
+            addr_of_mut!((*ptr).0).write(s);
+        }
+        // finish init
+        Ok(unsafe { uninit.assume_init() })
+    }
+}
+
+/// Inner trait to implement mutable ε-copy deserialization of a type.
+///
+/// The user should not implement this trait directly, but rather derive it.
+pub trait DeserializeMutInner: DeserializeInner {
+    type DeserMutType<'a>;
+
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> Result<Self::DeserMutType<'a>>;
+}
+
+/// Blanket implementation that prevents the user from overwriting the
+/// methods in [`DeserializeMut`].
+///
+/// This implementation [checks the header](`check_header`) written
+/// by the blanket implementation of [`crate::ser::Serialize`], unless
+/// [disabled](DeserializeOptions::header), and then delegates to
+/// [`DeserializeMutInner::_deserialize_eps_mut_inner`].
+impl<T: TypeHash + ReprHash + DeserializeMutInner> DeserializeMut for T {
+    fn deserialize_eps_mut_with<'a>(
+        options: &DeserializeOptions,
+        backend: &'a mut [u8],
+    ) -> Result<Self::DeserMutType<'a>> {
+        if options.checksum {
+            // The checksum cannot be computed once mutable references have been
+            // handed out, so we find the end of the data, and verify the
+            // checksum, with a preliminary ε-copy deserialization.
+            Self::deserialize_eps_with(options, backend)?;
+        }
+        let mut slice_with_pos = SliceWithPosMut::with_options(backend, *options);
+        if options.header {
+            check_header::<Self>(&mut slice_with_pos)?;
+        }
+        Self::_deserialize_eps_mut_inner(&mut slice_with_pos)
     }
 }
 
@@ -263,10 +497,27 @@ pub fn check_header<T: Deserialize>(backend: &mut impl ReadWithPos) -> Result<()
         return Err(Error::UsizeSizeMismatch(usize_size));
     };
 
+    // The minimum alignment used during serialization is adopted for the
+    // rest of the deserialization, if the backend supports it
+    let min_align = usize::_deserialize_full_inner(backend)?;
+    if !min_align.is_power_of_two() {
+        return Err(Error::InvalidValue("MIN_ALIGN"));
+    }
+    if backend.set_min_align(min_align).is_err() {
+        return Err(Error::MinAlignMismatch {
+            expected: backend.options().min_align,
+            got: min_align,
+        });
+    }
+
     let ser_type_hash = u64::_deserialize_full_inner(backend)?;
     let ser_repr_hash = u64::_deserialize_full_inner(backend)?;
     let ser_type_name = String::_deserialize_full_inner(backend)?;
 
+    if backend.options().validation == Validation::Basic {
+        return Ok(());
+    }
+
     if ser_type_hash != self_type_hash {
         return Err(Error::WrongTypeHash {
             got_type_name: self_type_name,
@@ -301,6 +552,17 @@ pub trait DeserializeHelper<T: CopySelector> {
     ) -> Result<Self::DeserType<'a>>;
 }
 
+/// A helper trait that makes it possible to implement differently
+/// mutable deserialization for [`crate::traits::ZeroCopy`] and [`crate::traits::DeepCopy`] types.
+/// See [`crate::traits::CopyType`] for more information.
+pub trait DeserializeMutHelper<T: CopySelector> {
+    type DeserMutType<'a>;
+
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> Result<Self::DeserMutType<'a>>;
+}
+
 #[derive(Debug)]
 /// Errors that can happen during deserialization.
 pub enum Error {
@@ -322,10 +584,20 @@ pub enum Error {
     /// For example, the file was serialized on a 64-bit machine and we are trying to
     /// deserialize it on a 32-bit machine.
     UsizeSizeMismatch(usize),
+    /// The [minimum alignment](DeserializeOptions::min_align) recorded in
+    /// the header (`got`) is different from the one used for deserialization
+    /// (`expected`), and the backend does not support
+    /// [setting it](ReadWithPos::set_min_align).
+    MinAlignMismatch { expected: usize, got: usize },
+    /// The backend does not support setting the given
+    /// [minimum alignment](ReadWithPos::set_min_align).
+    UnsupportedMinAlign(usize),
     /// The magic coookie is wrong. The byte sequence does not come from ε-serde.
     MagicCookieError(u64),
     /// A tag is wrong (e.g., for [`Option`]).
     InvalidTag(usize),
+    /// A value is not valid for its type (e.g., a zero [`NonZeroUsize`](core::num::NonZeroUsize)).
+    InvalidValue(&'static str),
     /// The type hash is wrong. Probably the user is trying to deserialize a
     /// file with the wrong type.
     WrongTypeHash {
@@ -344,6 +616,8 @@ pub enum Error {
         expected: u64,
         got: u64,
     },
+    /// The checksum following the serialized data does not match the data.
+    ChecksumMismatch { expected: u64, got: u64 },
 }
 
 impl std::error::Error for Error {}
@@ -390,8 +664,19 @@ impl core::fmt::Display for Error {
                 usize_size,
                 core::mem::size_of::<usize>()
             ),
+            Self::MinAlignMismatch { expected, got } => write!(
+                f,
+                "The data was serialized with minimum alignment {}, but it is being deserialized with minimum alignment {}.",
+                got, expected,
+            ),
+            Self::UnsupportedMinAlign(min_align) => write!(
+                f,
+                "The ε-serde backend does not support setting the minimum alignment to {}",
+                min_align
+            ),
             Self::AlignmentError => write!(f, "Alignment error. Most likely you are deserializing from a memory region with insufficient alignment."),
             Self::InvalidTag(tag) => write!(f, "Invalid tag: 0x{:02x}", tag),
+            Self::InvalidValue(ty) => write!(f, "Invalid value for type {}", ty),
             Self::WrongTypeHash {
                 got_type_name,
                 expected_type_name,
@@ -426,6 +711,11 @@ impl core::fmt::Display for Error {
                     expected, got, expected_type_name, got_type_name,
                 )
             }
+            Self::ChecksumMismatch { expected, got } => write!(
+                f,
+                "Wrong checksum. Expected: 0x{:016x} Actual: 0x{:016x}. The data might be corrupted.",
+                expected, got,
+            ),
         }
     }
 }
@@ -0,0 +1,135 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Options for deserialization.
+
+[`DeserializeOptions`] modifies the behavior of a single deserialization
+performed with
+[`Deserialize::deserialize_full_with`](super::Deserialize::deserialize_full_with),
+[`Deserialize::deserialize_eps_with`](super::Deserialize::deserialize_eps_with), or
+[`DeserializeMut::deserialize_eps_mut_with`](super::DeserializeMut::deserialize_eps_mut_with).
+The options are carried by the [`ReadWithPos`](super::ReadWithPos) backend,
+so implementations of [`DeserializeInner`](super::DeserializeInner) can
+access them using [`ReadWithPos::options`](super::ReadWithPos::options).
+
+The options must match the [`SerializeOptions`](crate::ser::SerializeOptions)
+used to serialize the data, except for the minimum alignment, which is read
+from the header, if present.
+
+*/
+
+/// How thoroughly serialized data is validated.
+///
+/// Bit patterns of zero-copy types are always checked, as accepting invalid
+/// values would be unsound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Validation {
+    /// Check the magic cookie, the version, and the size of `usize`,
+    /// but not the type hashes in the header.
+    Basic,
+    /// Check also the type hashes in the header.
+    #[default]
+    Full,
+}
+
+/// Options for [`Deserialize::deserialize_full_with`](super::Deserialize::deserialize_full_with),
+/// [`Deserialize::deserialize_eps_with`](super::Deserialize::deserialize_eps_with), and
+/// [`DeserializeMut::deserialize_eps_mut_with`](super::DeserializeMut::deserialize_eps_mut_with).
+///
+/// Options can be set by chaining methods starting from [`DeserializeOptions::new`]:
+/// ```
+/// # use epserde::deser::DeserializeOptions;
+/// let options = DeserializeOptions::new().min_align(64).checksum(true);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeserializeOptions {
+    /// Whether to read and [check the header](super::check_header). Defaults to `true`.
+    pub header: bool,
+    /// The minimum alignment of zero-copy data, which must be the same
+    /// used during serialization. If the header is read, the minimum
+    /// alignment recorded in the header is used instead. Defaults to 1.
+    pub min_align: usize,
+    /// Whether to verify the checksum following the serialized data.
+    /// Defaults to `false`.
+    pub checksum: bool,
+    /// How thoroughly the header is validated. Defaults to [`Validation::Full`].
+    pub validation: Validation,
+    /// The position of the first byte of the backend with respect to the start
+    /// of an enclosing container, which must be the same used during
+    /// serialization. Defaults to 0.
+    ///
+    /// Note that for ε-copy deserialization the enclosing container must be
+    /// suitably aligned in memory.
+    pub base_offset: usize,
+}
+
+impl DeserializeOptions {
+    /// The default options, which yield the same behavior of
+    /// [`Deserialize::deserialize_full`](super::Deserialize::deserialize_full),
+    /// [`Deserialize::deserialize_eps`](super::Deserialize::deserialize_eps), and
+    /// [`DeserializeMut::deserialize_eps_mut`](super::DeserializeMut::deserialize_eps_mut).
+    pub const DEFAULT: Self = Self {
+        header: true,
+        min_align: 1,
+        checksum: false,
+        validation: Validation::Full,
+        base_offset: 0,
+    };
+
+    /// Return the default options.
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Set whether to read the header.
+    pub const fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Set the minimum alignment of zero-copy data.
+    ///
+    /// # Panics
+    ///
+    /// If `min_align` is not a power of two.
+    pub const fn min_align(mut self, min_align: usize) -> Self {
+        assert!(
+            min_align.is_power_of_two(),
+            "The minimum alignment must be a power of two"
+        );
+        self.min_align = min_align;
+        self
+    }
+
+    /// Set whether to verify the checksum.
+    pub const fn checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Set the validation level.
+    pub const fn validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Set the position of the first byte of the backend with respect to the start
+    /// of an enclosing container.
+    pub const fn base_offset(mut self, base_offset: usize) -> Self {
+        self.base_offset = base_offset;
+        self
+    }
+}
+
+impl Default for DeserializeOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
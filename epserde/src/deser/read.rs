@@ -44,6 +44,30 @@ pub trait ReadWithPos: ReadNoStd + Sized {
     /// Return the current position.
     fn pos(&self) -> usize;
 
-    /// Pad the cursor to the next multiple of [`MaxSizeOf::max_size_of`] 'T'.
+    /// Pad the cursor to the next multiple of [`MaxSizeOf::max_size_of`] 'T',
+    /// or of the [minimum alignment](deser::DeserializeOptions::min_align) if
+    /// it is larger.
     fn align<T: MaxSizeOf>(&mut self) -> deser::Result<()>;
+
+    /// Return the [options](deser::DeserializeOptions) of the current deserialization.
+    ///
+    /// The default implementation returns [`DeserializeOptions::DEFAULT`](deser::DeserializeOptions::DEFAULT).
+    fn options(&self) -> &deser::DeserializeOptions {
+        &deser::DeserializeOptions::DEFAULT
+    }
+
+    /// Set the [minimum alignment](deser::DeserializeOptions::min_align) of the
+    /// current deserialization, which makes it possible to modify it
+    /// temporarily (see, e.g., [`with_min_align`](deser::helpers::with_min_align)).
+    ///
+    /// The default implementation, suitable for backends whose options
+    /// cannot be modified, returns [`UnsupportedMinAlign`](deser::Error::UnsupportedMinAlign)
+    /// unless `min_align` is the current minimum alignment.
+    fn set_min_align(&mut self, min_align: usize) -> deser::Result<()> {
+        if min_align == self.options().min_align {
+            Ok(())
+        } else {
+            Err(deser::Error::UnsupportedMinAlign(min_align))
+        }
+    }
 }
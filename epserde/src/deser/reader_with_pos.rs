@@ -7,7 +7,7 @@
 
 use crate::prelude::*;
 
-use super::ReadNoStd;
+use super::{DeserializeOptions, ReadNoStd};
 
 /// A wrapper for a [`ReadNoStd`] that implements [`ReadWithPos`]
 /// by keeping track of the current position.
@@ -16,13 +16,26 @@ pub struct ReaderWithPos<'a, F: ReadNoStd> {
     backend: &'a mut F,
     /// How many bytes we have read from the start
     pos: usize,
+    /// The options of the current deserialization
+    options: DeserializeOptions,
 }
 
 impl<'a, F: ReadNoStd> ReaderWithPos<'a, F> {
     #[inline(always)]
     /// Create a new [`ReadWithPos`] on top of a generic [`ReadNoStd`].
     pub fn new(backend: &'a mut F) -> Self {
-        Self { backend, pos: 0 }
+        Self::with_options(backend, DeserializeOptions::DEFAULT)
+    }
+
+    #[inline(always)]
+    /// Create a new [`ReadWithPos`] on top of a generic [`ReadNoStd`]
+    /// with the given [options](DeserializeOptions).
+    pub fn with_options(backend: &'a mut F, options: DeserializeOptions) -> Self {
+        Self {
+            backend,
+            pos: options.base_offset,
+            options,
+        }
     }
 }
 
@@ -41,9 +54,52 @@ impl<'a, F: ReadNoStd> ReadWithPos for ReaderWithPos<'a, F> {
 
     fn align<T: MaxSizeOf>(&mut self) -> deser::Result<()> {
         // Skip bytes as needed
-        let padding = crate::pad_align_to(self.pos, T::max_size_of());
+        let padding = crate::pad_align_to(self.pos, T::max_size_of().max(self.options.min_align));
         self.read_exact(&mut vec![0; padding])?;
         // No alignment check, we are fully deserializing
         Ok(())
     }
+
+    fn options(&self) -> &DeserializeOptions {
+        &self.options
+    }
+
+    fn set_min_align(&mut self, min_align: usize) -> deser::Result<()> {
+        self.options.min_align = min_align;
+        Ok(())
+    }
+}
+
+/// A wrapper for a [`ReadNoStd`] that computes a checksum of the read bytes.
+///
+/// It is used by [`Deserialize::deserialize_full_with`] to implement
+/// [`DeserializeOptions::checksum`].
+pub struct ChecksumReader<'a, F: ReadNoStd> {
+    /// What we actually read from
+    backend: &'a mut F,
+    /// The hasher of the bytes read so far
+    hasher: xxhash_rust::xxh3::Xxh3,
+}
+
+impl<'a, F: ReadNoStd> ChecksumReader<'a, F> {
+    /// Create a new [`ChecksumReader`] on top of a generic [`ReadNoStd`].
+    pub fn new(backend: &'a mut F) -> Self {
+        Self {
+            backend,
+            hasher: xxhash_rust::xxh3::Xxh3::new(),
+        }
+    }
+
+    /// Return the checksum of the bytes read so far.
+    pub fn checksum(&self) -> u64 {
+        self.hasher.digest()
+    }
+}
+
+impl<F: ReadNoStd> ReadNoStd for ChecksumReader<'_, F> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> deser::Result<()> {
+        self.backend.read_exact(buf)?;
+        self.hasher.update(buf);
+        Ok(())
+    }
 }
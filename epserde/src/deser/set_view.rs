@@ -0,0 +1,106 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Set views, the ε-copy deserialization type of sets.
+
+Sets such as `BTreeSet<T>` and `HashSet<T>` are serialized as a
+sorted sequence of elements, with the layout of a `Vec<T>`. They
+are ε-copy deserialized as a [`SetView`] wrapping the deserialization type of
+`Vec<T>` (e.g., a slice, or a [`StrPool`] for strings), which provides
+membership queries by binary search.
+
+To keep ε-copy deserialization constant time, the order of the elements
+is not checked: membership queries assume that the serialized data comes
+from a trusted source (see [`SetView::contains`]).
+
+*/
+
+use super::{StrPool, StrPoolMut};
+use core::ops::Deref;
+
+/// A sorted sequence that can be searched for values of type `Q`.
+pub trait BinarySearch<Q: ?Sized> {
+    /// Binary search the sequence for the given value, with the
+    /// same semantics of [`slice::binary_search`].
+    fn binary_search(&self, value: &Q) -> Result<usize, usize>;
+}
+
+impl<T: Ord, S: AsRef<[T]>> BinarySearch<T> for S {
+    #[inline(always)]
+    fn binary_search(&self, value: &T) -> Result<usize, usize> {
+        self.as_ref().binary_search(value)
+    }
+}
+
+impl BinarySearch<str> for StrPool<'_> {
+    #[inline(always)]
+    fn binary_search(&self, value: &str) -> Result<usize, usize> {
+        let (mut low, mut high) = (0, self.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.get(mid).cmp(value) {
+                core::cmp::Ordering::Less => low = mid + 1,
+                core::cmp::Ordering::Greater => high = mid,
+                core::cmp::Ordering::Equal => return Ok(mid),
+            }
+        }
+        Err(low)
+    }
+}
+
+impl BinarySearch<str> for StrPoolMut<'_> {
+    #[inline(always)]
+    fn binary_search(&self, value: &str) -> Result<usize, usize> {
+        self.as_pool().binary_search(value)
+    }
+}
+
+/// A borrowed view on a set, stored as a sorted sequence.
+///
+/// The view dereferences to the underlying sequence, so, for example,
+/// its elements can be accessed by
+/// iteration. Membership can be tested with [`contains`](SetView::contains).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SetView<S>(S);
+
+impl<S> SetView<S> {
+    pub(crate) fn new(sorted: S) -> Self {
+        Self(sorted)
+    }
+
+    /// Return true if the set contains the given value.
+    ///
+    /// This method assumes that the underlying sequence is sorted, which
+    /// is true for sequences serialized by ε-serde, but it is not checked
+    /// during deserialization. On corrupted or maliciously crafted data the
+    /// result is unspecified, but the behavior is still safe. If the data
+    /// comes from an untrusted source, you can check that the sequence is
+    /// sorted before using this method.
+    #[inline(always)]
+    pub fn contains<Q: ?Sized>(&self, value: &Q) -> bool
+    where
+        S: BinarySearch<Q>,
+    {
+        self.0.binary_search(value).is_ok()
+    }
+
+    /// Return the underlying sorted sequence.
+    #[inline(always)]
+    pub fn into_inner(self) -> S {
+        self.0
+    }
+}
+
+impl<S> Deref for SetView<S> {
+    type Target = S;
+    #[inline(always)]
+    fn deref(&self) -> &S {
+        &self.0
+    }
+}
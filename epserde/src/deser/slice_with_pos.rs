@@ -14,13 +14,20 @@ use crate::prelude::*;
 pub struct SliceWithPos<'a> {
     pub data: &'a [u8],
     pub pos: usize,
+    pub options: DeserializeOptions,
 }
 
 impl<'a> SliceWithPos<'a> {
     pub fn new(backend: &'a [u8]) -> Self {
+        Self::with_options(backend, DeserializeOptions::DEFAULT)
+    }
+
+    /// Create a new [`SliceWithPos`] with the given [options](DeserializeOptions).
+    pub fn with_options(backend: &'a [u8], options: DeserializeOptions) -> Self {
         Self {
             data: backend,
-            pos: 0,
+            pos: options.base_offset,
+            options,
         }
     }
 
@@ -54,13 +61,23 @@ impl<'a> ReadWithPos for SliceWithPos<'a> {
     /// the absolute memory position is properly aligned.
     fn align<T: MaxSizeOf>(&mut self) -> deser::Result<()> {
         // Skip bytes as needed
-        let padding = crate::pad_align_to(self.pos, T::max_size_of());
+        let align = T::max_size_of().max(self.options.min_align);
+        let padding = crate::pad_align_to(self.pos, align);
         self.skip(padding);
         // Check that the ptr is indeed aligned
-        if self.data.as_ptr() as usize % T::max_size_of() != 0 {
+        if !(self.data.as_ptr() as usize).is_multiple_of(align) {
             Err(Error::AlignmentError)
         } else {
             Ok(())
         }
     }
+
+    fn options(&self) -> &DeserializeOptions {
+        &self.options
+    }
+
+    fn set_min_align(&mut self, min_align: usize) -> deser::Result<()> {
+        self.options.min_align = min_align;
+        Ok(())
+    }
 }
@@ -0,0 +1,93 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use super::*;
+use crate::prelude::*;
+
+/// Mutable version of [`SliceWithPos`], used for mutable ε-copy deserialization.
+///
+/// Contrarily to [`SliceWithPos`], the data is consumed by splitting off
+/// the bytes that have been read, so that mutable references to different
+/// parts of the original slice can be handed out.
+#[derive(Debug)]
+pub struct SliceWithPosMut<'a> {
+    pub data: &'a mut [u8],
+    pub pos: usize,
+    pub options: DeserializeOptions,
+}
+
+impl<'a> SliceWithPosMut<'a> {
+    pub fn new(backend: &'a mut [u8]) -> Self {
+        Self::with_options(backend, DeserializeOptions::DEFAULT)
+    }
+
+    /// Create a new [`SliceWithPosMut`] with the given [options](DeserializeOptions).
+    pub fn with_options(backend: &'a mut [u8], options: DeserializeOptions) -> Self {
+        Self {
+            data: backend,
+            pos: options.base_offset,
+            options,
+        }
+    }
+
+    /// Split off the next `bytes` bytes, returning them with the lifetime
+    /// of the underlying slice.
+    pub fn split(&mut self, bytes: usize) -> &'a mut [u8] {
+        let data = core::mem::take(&mut self.data);
+        let (head, tail) = data.split_at_mut(bytes);
+        self.data = tail;
+        self.pos += bytes;
+        head
+    }
+
+    pub fn skip(&mut self, bytes: usize) {
+        self.split(bytes);
+    }
+}
+
+impl<'a> ReadNoStd for SliceWithPosMut<'a> {
+    fn read_exact(&mut self, buf: &mut [u8]) -> deser::Result<()> {
+        let len = buf.len();
+        if len > self.data.len() {
+            return Err(Error::ReadError);
+        }
+        buf.copy_from_slice(self.split(len));
+        Ok(())
+    }
+}
+
+impl<'a> ReadWithPos for SliceWithPosMut<'a> {
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Pad the cursor to the correct alignment.
+    ///
+    /// Note that this method also checks that
+    /// the absolute memory position is properly aligned.
+    fn align<T: MaxSizeOf>(&mut self) -> deser::Result<()> {
+        // Skip bytes as needed
+        let align = T::max_size_of().max(self.options.min_align);
+        let padding = crate::pad_align_to(self.pos, align);
+        self.skip(padding);
+        // Check that the ptr is indeed aligned
+        if !(self.data.as_ptr() as usize).is_multiple_of(align) {
+            Err(Error::AlignmentError)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn options(&self) -> &DeserializeOptions {
+        &self.options
+    }
+
+    fn set_min_align(&mut self, min_align: usize) -> deser::Result<()> {
+        self.options.min_align = min_align;
+        Ok(())
+    }
+}
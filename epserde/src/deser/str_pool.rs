@@ -0,0 +1,340 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+String pools, the ε-copy deserialization type of vectors and boxed slices
+of strings.
+
+A vector `Vec<String>` (or a boxed slice `Box<[Box<str>]>`) is serialized
+as the offsets of its strings in their concatenation, followed by
+the concatenation itself. This layout can be ε-copy deserialized
+without allocation as a [`StrPool`] (or as a [`StrPoolMut`]), which
+provides random access to the strings. Deserialization checks in constant
+time that the offsets delimit the concatenation; each string is checked
+to be delimited by valid offsets and to be valid UTF-8 when it is accessed,
+so that access time is linear in the length of the string. A whole pool
+can be checked in advance using [`StrPool::check`].
+
+*/
+
+use crate::deser;
+use core::fmt;
+use core::ops::Index;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, vec::Vec};
+
+/// Check that offsets and data form a string pool, that is, that `data` is
+/// valid UTF-8, and that `offsets` is a nondecreasing sequence of character
+/// boundaries of `data` starting at zero and ending at the length of `data`.
+pub(crate) fn check_pool(offsets: &[usize], data: &[u8]) -> deser::Result<()> {
+    let data = core::str::from_utf8(data).map_err(|_| deser::Error::InvalidValue("StrPool"))?;
+    if offsets.first() != Some(&0)
+        || offsets.last() != Some(&data.len())
+        || offsets
+            .windows(2)
+            .any(|w| w[0] > w[1] || !data.is_char_boundary(w[1]))
+    {
+        return Err(deser::Error::InvalidValue("StrPool"));
+    }
+    Ok(())
+}
+
+/// An immutable view on a sequence of strings stored as a list of
+/// offsets and a byte slice containing their concatenation.
+///
+/// This is the ε-copy deserialization type of `Vec<String>`,
+/// `Vec<Box<str>>`, `Box<[String]>`, and `Box<[Box<str>]>`.
+///
+/// Strings are [checked](StrPool::try_get) when they are accessed: methods
+/// returning strings without a [`Result`](deser::Result), including
+/// iteration and comparison, panic on invalid strings.
+#[derive(Clone, Copy)]
+pub struct StrPool<'a> {
+    offsets: &'a [usize],
+    data: &'a [u8],
+}
+
+/// Check in constant time that `offsets` starts at zero and ends at the
+/// length of `data`.
+fn check_bounds(offsets: &[usize], data: &[u8]) -> deser::Result<()> {
+    if offsets.first() != Some(&0) || offsets.last() != Some(&data.len()) {
+        return Err(deser::Error::InvalidValue("StrPool"));
+    }
+    Ok(())
+}
+
+/// Return the range of the string of given index in the concatenation.
+///
+/// # Panics
+///
+/// If `index + 1` is not smaller than `offsets.len()`.
+#[inline(always)]
+fn str_range(offsets: &[usize], index: usize) -> core::ops::Range<usize> {
+    offsets[index]..offsets[index + 1]
+}
+
+impl<'a> StrPool<'a> {
+    pub(crate) fn new(offsets: &'a [usize], data: &'a [u8]) -> deser::Result<Self> {
+        check_bounds(offsets, data)?;
+        Ok(Self { offsets, data })
+    }
+
+    /// Check the whole pool, that is, that all strings are delimited by
+    /// valid offsets and are valid UTF-8.
+    ///
+    /// This method requires time linear in the size of the pool. If it
+    /// returns `Ok`, no method of this pool will panic on invalid strings.
+    pub fn check(&self) -> deser::Result<()> {
+        check_pool(self.offsets, self.data)
+    }
+
+    /// Return the number of strings in the pool.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Return true if the pool contains no strings.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the string of given index.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`len`](StrPool::len), or if the
+    /// string is [invalid](StrPool::try_get).
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> &'a str {
+        self.try_get(index).expect("invalid string in string pool")
+    }
+
+    /// Return the string of given index, or an error if its offsets are
+    /// not valid or if it is not valid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`len`](StrPool::len).
+    #[inline(always)]
+    pub fn try_get(&self, index: usize) -> deser::Result<&'a str> {
+        self.data
+            .get(str_range(self.offsets, index))
+            .and_then(|bytes| core::str::from_utf8(bytes).ok())
+            .ok_or(deser::Error::InvalidValue("StrPool"))
+    }
+
+    /// Return an iterator over the strings of the pool.
+    #[inline(always)]
+    pub fn iter(&self) -> StrPoolIter<'a> {
+        StrPoolIter {
+            pool: *self,
+            range: 0..self.len(),
+        }
+    }
+}
+
+impl<'a> Index<usize> for StrPool<'a> {
+    type Output = str;
+    #[inline(always)]
+    fn index(&self, index: usize) -> &str {
+        self.get(index)
+    }
+}
+
+impl<'a> IntoIterator for StrPool<'a> {
+    type Item = &'a str;
+    type IntoIter = StrPoolIter<'a>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &StrPool<'a> {
+    type Item = &'a str;
+    type IntoIter = StrPoolIter<'a>;
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl fmt::Debug for StrPool<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for StrPool<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for StrPool<'_> {}
+
+impl<S: AsRef<str>> PartialEq<[S]> for StrPool<'_> {
+    fn eq(&self, other: &[S]) -> bool {
+        self.iter().eq(other.iter().map(AsRef::as_ref))
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<Vec<S>> for StrPool<'_> {
+    fn eq(&self, other: &Vec<S>) -> bool {
+        *self == **other
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<StrPool<'_>> for Vec<S> {
+    fn eq(&self, other: &StrPool<'_>) -> bool {
+        *other == **self
+    }
+}
+
+impl<S: AsRef<str>> PartialEq<StrPool<'_>> for Box<[S]> {
+    fn eq(&self, other: &StrPool<'_>) -> bool {
+        *other == **self
+    }
+}
+
+/// An iterator over the strings of a [`StrPool`].
+#[derive(Debug, Clone)]
+pub struct StrPoolIter<'a> {
+    pool: StrPool<'a>,
+    range: core::ops::Range<usize>,
+}
+
+impl<'a> Iterator for StrPoolIter<'a> {
+    type Item = &'a str;
+    #[inline(always)]
+    fn next(&mut self) -> Option<&'a str> {
+        self.range.next().map(|index| self.pool.get(index))
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for StrPoolIter<'a> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<&'a str> {
+        self.range.next_back().map(|index| self.pool.get(index))
+    }
+}
+
+impl ExactSizeIterator for StrPoolIter<'_> {}
+
+/// A mutable view on a sequence of strings stored as a list of
+/// offsets and a byte slice containing their concatenation.
+///
+/// This is the mutable ε-copy deserialization type of `Vec<String>`,
+/// `Vec<Box<str>>`, `Box<[String]>`, and `Box<[Box<str>]>`. The length
+/// of the strings cannot be modified.
+///
+/// As in the case of [`StrPool`], strings are checked when they are accessed.
+pub struct StrPoolMut<'a> {
+    offsets: &'a [usize],
+    data: &'a mut [u8],
+}
+
+impl<'a> StrPoolMut<'a> {
+    pub(crate) fn new(offsets: &'a [usize], data: &'a mut [u8]) -> deser::Result<Self> {
+        check_bounds(offsets, data)?;
+        Ok(Self { offsets, data })
+    }
+
+    /// Check the whole pool, as [`StrPool::check`].
+    pub fn check(&self) -> deser::Result<()> {
+        self.as_pool().check()
+    }
+
+    /// Return the number of strings in the pool.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Return true if the pool contains no strings.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the string of given index.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`len`](StrPoolMut::len), or if the
+    /// string is [invalid](StrPool::try_get).
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> &str {
+        self.as_pool().get(index)
+    }
+
+    /// Return the string of given index, as [`StrPool::try_get`].
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`len`](StrPoolMut::len).
+    #[inline(always)]
+    pub fn try_get(&self, index: usize) -> deser::Result<&str> {
+        self.as_pool().try_get(index)
+    }
+
+    /// Return the string of given index as a mutable reference.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`len`](StrPoolMut::len), or if the
+    /// string is [invalid](StrPool::try_get).
+    #[inline(always)]
+    pub fn get_mut(&mut self, index: usize) -> &mut str {
+        self.try_get_mut(index)
+            .expect("invalid string in string pool")
+    }
+
+    /// Return the string of given index as a mutable reference, or an error
+    /// if its offsets are not valid or if it is not valid UTF-8.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not smaller than [`len`](StrPoolMut::len).
+    #[inline(always)]
+    pub fn try_get_mut(&mut self, index: usize) -> deser::Result<&mut str> {
+        self.data
+            .get_mut(str_range(self.offsets, index))
+            .and_then(|bytes| core::str::from_utf8_mut(bytes).ok())
+            .ok_or(deser::Error::InvalidValue("StrPool"))
+    }
+
+    /// Return an iterator over the strings of the pool.
+    #[inline(always)]
+    pub fn iter(&self) -> StrPoolIter<'_> {
+        self.as_pool().iter()
+    }
+
+    /// Return an immutable view on the pool.
+    #[inline(always)]
+    pub fn as_pool(&self) -> StrPool<'_> {
+        StrPool {
+            offsets: self.offsets,
+            data: self.data,
+        }
+    }
+}
+
+impl fmt::Debug for StrPoolMut<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_pool().fmt(f)
+    }
+}
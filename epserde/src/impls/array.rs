@@ -17,7 +17,13 @@ use deser::*;
 use ser::*;
 
 impl<T: CopyType, const N: usize> CopyType for [T; N] {
-    type Copy = T::Copy;
+    // Normalize the selector to Zero or Deep, as arrays of strings
+    // are not pooled
+    type Copy = <T::Copy as CopySelector>::And<Zero>;
+}
+
+impl<T: CopyType<Copy = Zero>, const N: usize> ZeroOptionCopyType for [T; N] {
+    type OptionCopy = Deep;
 }
 
 impl<T: TypeHash, const N: usize> TypeHash for [T; N] {
@@ -42,7 +48,7 @@ impl<T: MaxSizeOf, const N: usize> MaxSizeOf for [T; N] {
 
 impl<T: CopyType + SerializeInner + TypeHash, const N: usize> SerializeInner for [T; N]
 where
-    [T; N]: SerializeHelper<<T as CopyType>::Copy>,
+    [T; N]: SerializeHelper<<[T; N] as CopyType>::Copy>,
 {
     const IS_ZERO_COPY: bool = T::IS_ZERO_COPY;
     const ZERO_COPY_MISMATCH: bool = T::ZERO_COPY_MISMATCH;
@@ -58,7 +64,7 @@ impl<T: ZeroCopy + SerializeInner + TypeHash, const N: usize> SerializeHelper<Ze
     }
 }
 
-impl<T: DeepCopy + SerializeInner, const N: usize> SerializeHelper<Deep> for [T; N] {
+impl<T: CopyType + SerializeInner, const N: usize> SerializeHelper<Deep> for [T; N] {
     #[inline(always)]
     fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
         for item in self.iter() {
@@ -70,19 +76,36 @@ impl<T: DeepCopy + SerializeInner, const N: usize> SerializeHelper<Deep> for [T;
 
 impl<T: CopyType + DeserializeInner + 'static, const N: usize> DeserializeInner for [T; N]
 where
-    [T; N]: DeserializeHelper<<T as CopyType>::Copy, FullType = [T; N]>,
+    [T; N]: DeserializeHelper<<[T; N] as CopyType>::Copy, FullType = [T; N]>,
 {
-    type DeserType<'a> = <[T; N] as DeserializeHelper<<T as CopyType>::Copy>>::DeserType<'a>;
+    const MUST_CHECK_ZERO_COPY: bool = T::MUST_CHECK_ZERO_COPY;
+    type DeserType<'a> = <[T; N] as DeserializeHelper<<[T; N] as CopyType>::Copy>>::DeserType<'a>;
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        <[T; N] as DeserializeHelper<<T as CopyType>::Copy>>::_deserialize_full_inner_impl(backend)
+        <[T; N] as DeserializeHelper<<[T; N] as CopyType>::Copy>>::_deserialize_full_inner_impl(
+            backend,
+        )
     }
 
     #[inline(always)]
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
-    ) -> deser::Result<<[T; N] as DeserializeHelper<<T as CopyType>::Copy>>::DeserType<'a>> {
-        <[T; N] as DeserializeHelper<<T as CopyType>::Copy>>::_deserialize_eps_inner_impl(backend)
+    ) -> deser::Result<<[T; N] as DeserializeHelper<<[T; N] as CopyType>::Copy>>::DeserType<'a>>
+    {
+        <[T; N] as DeserializeHelper<<[T; N] as CopyType>::Copy>>::_deserialize_eps_inner_impl(
+            backend,
+        )
+    }
+
+    #[inline(always)]
+    fn _check_zero_copy(bytes: &[u8]) -> deser::Result<()> {
+        let size = core::mem::size_of::<T>();
+        if T::MUST_CHECK_ZERO_COPY && size != 0 {
+            for chunk in bytes.chunks_exact(size) {
+                T::_check_zero_copy(chunk)?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -93,19 +116,26 @@ impl<T: ZeroCopy + DeserializeInner + 'static, const N: usize> DeserializeHelper
     fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
         let mut res = MaybeUninit::<[T; N]>::uninit();
         backend.align::<T>()?;
-        // SAFETY: read_exact guarantees that the array will be filled with data.
+        // SAFETY: read_exact guarantees that the array will be filled with data,
+        // and we check that the data represents valid values before assuming
+        // initialization.
         unsafe {
-            backend.read_exact(res.assume_init_mut().align_to_mut::<u8>().1)?;
+            let bytes = core::slice::from_raw_parts_mut(
+                res.as_mut_ptr() as *mut u8,
+                core::mem::size_of::<[T; N]>(),
+            );
+            backend.read_exact(bytes)?;
+            check_slice_zero::<T>(bytes)?;
             Ok(res.assume_init())
         }
     }
     #[inline(always)]
-
     fn _deserialize_eps_inner_impl<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<<Self as DeserializeInner>::DeserType<'a>> {
         backend.align::<T>()?;
         let bytes = std::mem::size_of::<[T; N]>();
+        check_slice_zero::<T>(&backend.data[..bytes])?;
         let (pre, data, after) = unsafe { backend.data[..bytes].align_to::<[T; N]>() };
         debug_assert!(pre.is_empty());
         debug_assert!(after.is_empty());
@@ -115,7 +145,7 @@ impl<T: ZeroCopy + DeserializeInner + 'static, const N: usize> DeserializeHelper
     }
 }
 
-impl<T: DeepCopy + DeserializeInner + 'static, const N: usize> DeserializeHelper<Deep> for [T; N] {
+impl<T: CopyType + DeserializeInner + 'static, const N: usize> DeserializeHelper<Deep> for [T; N] {
     type FullType = Self;
     type DeserType<'a> = [<T as DeserializeInner>::DeserType<'a>; N];
     #[inline(always)]
@@ -131,8 +161,8 @@ impl<T: DeepCopy + DeserializeInner + 'static, const N: usize> DeserializeHelper
     #[inline(always)]
     fn _deserialize_eps_inner_impl<'a>(
         backend: &mut SliceWithPos<'a>,
-    ) -> deser::Result<<Self as DeserializeInner>::DeserType<'a>> {
-        let mut res = MaybeUninit::<<Self as DeserializeInner>::DeserType<'_>>::uninit();
+    ) -> deser::Result<<Self as DeserializeHelper<Deep>>::DeserType<'a>> {
+        let mut res = MaybeUninit::<<Self as DeserializeHelper<Deep>>::DeserType<'_>>::uninit();
         unsafe {
             for item in &mut res.assume_init_mut().iter_mut() {
                 std::ptr::write(item, T::_deserialize_eps_inner(backend)?);
@@ -141,3 +171,57 @@ impl<T: DeepCopy + DeserializeInner + 'static, const N: usize> DeserializeHelper
         }
     }
 }
+
+impl<T: CopyType + DeserializeMutInner + 'static, const N: usize> DeserializeMutInner for [T; N]
+where
+    [T; N]: DeserializeHelper<<[T; N] as CopyType>::Copy, FullType = [T; N]>,
+    [T; N]: DeserializeMutHelper<<[T; N] as CopyType>::Copy>,
+{
+    type DeserMutType<'a> =
+        <[T; N] as DeserializeMutHelper<<[T; N] as CopyType>::Copy>>::DeserMutType<'a>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<<[T; N] as DeserializeMutHelper<<[T; N] as CopyType>::Copy>>::DeserMutType<'a>>
+    {
+        <[T; N] as DeserializeMutHelper<<[T; N] as CopyType>::Copy>>::_deserialize_eps_mut_inner_impl(
+            backend,
+        )
+    }
+}
+
+impl<T: ZeroCopy + DeserializeInner + 'static, const N: usize> DeserializeMutHelper<Zero>
+    for [T; N]
+{
+    type DeserMutType<'a> = &'a mut [T; N];
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        backend.align::<T>()?;
+        let bytes = std::mem::size_of::<[T; N]>();
+        check_slice_zero::<T>(&backend.data[..bytes])?;
+        let (pre, data, after) = unsafe { backend.split(bytes).align_to_mut::<[T; N]>() };
+        debug_assert!(pre.is_empty());
+        debug_assert!(after.is_empty());
+        Ok(&mut data[0])
+    }
+}
+
+impl<T: CopyType + DeserializeMutInner + 'static, const N: usize> DeserializeMutHelper<Deep>
+    for [T; N]
+{
+    type DeserMutType<'a> = [<T as DeserializeMutInner>::DeserMutType<'a>; N];
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        let mut res = MaybeUninit::<Self::DeserMutType<'_>>::uninit();
+        unsafe {
+            for item in &mut res.assume_init_mut().iter_mut() {
+                std::ptr::write(item, T::_deserialize_eps_mut_inner(backend)?);
+            }
+            Ok(res.assume_init())
+        }
+    }
+}
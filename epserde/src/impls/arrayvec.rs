@@ -0,0 +1,43 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Implementations for [`ArrayVec`] (available with the `arrayvec` feature).
+
+Array vectors are serialized with the layout of a `Vec<T>`, and they have the
+same ε-copy deserialization type of `Vec<T>` (e.g., a slice if `T` is
+zero-copy). Full deserialization returns an array vector, and fails with
+[`deser::Error::InvalidValue`] if the elements do not fit its capacity.
+
+*/
+
+use crate::impls::vec::impl_vec_layout;
+use crate::prelude::*;
+use ::arrayvec::ArrayVec;
+use core::hash::Hash;
+
+impl<T: TypeHash, const CAP: usize> TypeHash for ArrayVec<T, CAP> {
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "ArrayVec".hash(hasher);
+        T::type_hash(hasher);
+    }
+}
+
+impl<T: ReprHash, const CAP: usize> ReprHash for ArrayVec<T, CAP> {
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        *offset_of = 0;
+        T::repr_hash(hasher, offset_of);
+    }
+}
+
+impl_vec_layout!([T, const CAP: usize] ArrayVec<T, CAP>, T, |v: Vec<T>| {
+    if v.len() > CAP {
+        return Err(deser::Error::InvalidValue("ArrayVec"));
+    }
+    Ok(v.into_iter().collect())
+});
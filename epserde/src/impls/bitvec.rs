@@ -0,0 +1,125 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Implementations for bit vectors of the [`bitvec`](::bitvec) crate
+(available with the `bitvec` feature).
+
+A [`BitVec<T, O>`] whose storage type `T` is zero-copy is serialized
+as the bit offset of its first bit in the first storage element, its length in
+bits, and its storage, with the layout of a `Vec<T>`. Bit vectors are ε-copy
+deserialized as a reference to a [`BitSlice<T, O>`].
+
+*/
+
+use crate::deser::helpers::*;
+use crate::prelude::*;
+use ::bitvec::mem::bits_of;
+use ::bitvec::prelude::*;
+use core::hash::Hash;
+use deser::*;
+use ser::*;
+
+impl TypeHash for Lsb0 {
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "Lsb0".hash(hasher);
+    }
+}
+
+impl TypeHash for Msb0 {
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "Msb0".hash(hasher);
+    }
+}
+
+impl<T: BitStore, O: BitOrder> CopyType for BitVec<T, O> {
+    type Copy = Deep;
+}
+
+impl<T: BitStore + TypeHash, O: BitOrder + TypeHash> TypeHash for BitVec<T, O> {
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "BitVec".hash(hasher);
+        T::type_hash(hasher);
+        O::type_hash(hasher);
+    }
+}
+
+impl<T: BitStore + ReprHash, O: BitOrder> ReprHash for BitVec<T, O> {
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        *offset_of = 0;
+        T::repr_hash(hasher, offset_of);
+    }
+}
+
+impl<T: BitStore + ZeroCopy + SerializeInner, O: BitOrder> SerializeInner for BitVec<T, O> {
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        let head = self.as_bitptr().bit().into_inner() as usize;
+        backend.write("head", &head)?;
+        backend.write("bit_len", &self.len())?;
+        serialize_slice_zero(backend, self.as_raw_slice())
+    }
+}
+
+/// Check that a bit range fits the given storage.
+fn check_bits<T: BitStore>(head: usize, len: usize, raw: &[T]) -> deser::Result<()> {
+    match head.checked_add(len) {
+        Some(end) if head < bits_of::<T>() && end <= raw.len() * bits_of::<T>() => Ok(()),
+        _ => Err(deser::Error::InvalidValue("BitVec")),
+    }
+}
+
+impl<T: BitStore + ZeroCopy + DeserializeInner + 'static, O: BitOrder + 'static> DeserializeInner
+    for BitVec<T, O>
+{
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        let head = usize::_deserialize_full_inner(backend)?;
+        let len = usize::_deserialize_full_inner(backend)?;
+        let raw = deserialize_full_vec_zero::<T>(backend)?;
+        check_bits(head, len, &raw)?;
+        let mut bit_vec =
+            BitVec::try_from_vec(raw).map_err(|_| deser::Error::InvalidValue("BitVec"))?;
+        if head == 0 {
+            bit_vec.truncate(len);
+            Ok(bit_vec)
+        } else {
+            Ok(BitVec::from_bitslice(&bit_vec[head..head + len]))
+        }
+    }
+    type DeserType<'a> = &'a BitSlice<T, O>;
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        let head = usize::_deserialize_full_inner(backend)?;
+        let len = usize::_deserialize_full_inner(backend)?;
+        let raw = deserialize_eps_slice_zero::<T>(backend)?;
+        check_bits(head, len, raw)?;
+        let bit_slice = BitSlice::<T, O>::try_from_slice(raw)
+            .map_err(|_| deser::Error::InvalidValue("BitVec"))?;
+        Ok(&bit_slice[head..head + len])
+    }
+}
+
+impl<T: BitStore + ZeroCopy + DeserializeInner + 'static, O: BitOrder + 'static> DeserializeMutInner
+    for BitVec<T, O>
+{
+    type DeserMutType<'a> = &'a mut BitSlice<T, O>;
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        let head = usize::_deserialize_full_inner(backend)?;
+        let len = usize::_deserialize_full_inner(backend)?;
+        let raw = deserialize_eps_mut_slice_zero::<T>(backend)?;
+        check_bits(head, len, raw)?;
+        let bit_slice = BitSlice::<T, O>::try_from_slice_mut(raw)
+            .map_err(|_| deser::Error::InvalidValue("BitVec"))?;
+        Ok(&mut bit_slice[head..head + len])
+    }
+}
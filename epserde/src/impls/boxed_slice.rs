@@ -59,6 +59,23 @@ impl<T: DeepCopy + SerializeInner> SerializeHelper<Deep> for Box<[T]> {
     }
 }
 
+impl<T: CopyType<Copy = Pool> + SerializeInner + AsRef<str>> SerializeHelper<Pool> for Box<[T]> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_iter_pool(backend, self.iter())
+    }
+}
+
+impl<T: AtomicCopy> SerializeHelper<Atomic> for Box<[T]>
+where
+    T::NonAtomic: SerializeInner,
+{
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_slice_atomic(backend, self)
+    }
+}
+
 // This delegates to a private helper trait which we can specialize on in stable rust
 impl<T: DeserializeInner + CopyType + 'static> DeserializeInner for Box<[T]>
 where
@@ -109,3 +126,101 @@ impl<T: DeepCopy + DeserializeInner + 'static> DeserializeHelper<Deep> for Box<[
         Ok(deserialize_eps_vec_deep::<T>(backend)?.into_boxed_slice())
     }
 }
+
+impl<T: CopyType<Copy = Pool> + DeserializeInner + for<'b> From<&'b str> + 'static>
+    DeserializeHelper<Pool> for Box<[T]>
+{
+    type FullType = Self;
+    type DeserType<'a> = StrPool<'a>;
+    #[inline(always)]
+    fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(deserialize_full_vec_pool::<T>(backend)?.into_boxed_slice())
+    }
+    #[inline(always)]
+    fn _deserialize_eps_inner_impl<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<<Self as DeserializeInner>::DeserType<'a>> {
+        deserialize_eps_pool(backend)
+    }
+}
+
+impl<T: AtomicCopy + 'static> DeserializeHelper<Atomic> for Box<[T]>
+where
+    T::NonAtomic: DeserializeInner,
+{
+    type FullType = Self;
+    type DeserType<'a> = &'a [T::NonAtomic];
+    #[inline(always)]
+    fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(deserialize_full_vec_atomic(backend)?.into_boxed_slice())
+    }
+    #[inline(always)]
+    fn _deserialize_eps_inner_impl<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        deserialize_eps_slice_zero(backend)
+    }
+}
+
+impl<T: DeserializeMutInner + CopyType + 'static> DeserializeMutInner for Box<[T]>
+where
+    Box<[T]>: DeserializeHelper<<T as CopyType>::Copy, FullType = Box<[T]>>,
+    Box<[T]>: DeserializeMutHelper<<T as CopyType>::Copy>,
+{
+    type DeserMutType<'a> =
+        <Box<[T]> as DeserializeMutHelper<<T as CopyType>::Copy>>::DeserMutType<'a>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<<Box<[T]> as DeserializeMutHelper<<T as CopyType>::Copy>>::DeserMutType<'a>>
+    {
+        <Box<[T]> as DeserializeMutHelper<<T as CopyType>::Copy>>::_deserialize_eps_mut_inner_impl(
+            backend,
+        )
+    }
+}
+
+impl<T: ZeroCopy + DeserializeInner + 'static> DeserializeMutHelper<Zero> for Box<[T]> {
+    type DeserMutType<'a> = &'a mut [T];
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        deserialize_eps_mut_slice_zero(backend)
+    }
+}
+
+impl<T: DeepCopy + DeserializeMutInner + 'static> DeserializeMutHelper<Deep> for Box<[T]> {
+    type DeserMutType<'a> = Box<[<T as DeserializeMutInner>::DeserMutType<'a>]>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Ok(deserialize_eps_mut_vec_deep::<T>(backend)?.into_boxed_slice())
+    }
+}
+
+impl<T: CopyType<Copy = Pool> + DeserializeMutInner + for<'b> From<&'b str> + 'static>
+    DeserializeMutHelper<Pool> for Box<[T]>
+{
+    type DeserMutType<'a> = StrPoolMut<'a>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        deserialize_eps_mut_pool(backend)
+    }
+}
+
+impl<T: AtomicCopy + 'static> DeserializeMutHelper<Atomic> for Box<[T]>
+where
+    T::NonAtomic: DeserializeInner,
+{
+    type DeserMutType<'a> = &'a mut [T];
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        deserialize_eps_mut_slice_atomic(backend)
+    }
+}
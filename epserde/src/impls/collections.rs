@@ -0,0 +1,374 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Implementations for [`VecDeque`], [`BinaryHeap`], [`BTreeSet`] and [`HashSet`].
+
+All these collections are serialized with the layout of a `Vec<T>`, and
+deserialized by means of the implementation for `Vec<T>`:
+
+- deques are serialized in order, and they have the same ε-copy
+  deserialization type of `Vec<T>` (e.g., a slice if `T` is zero-copy);
+
+- binary heaps are serialized in their internal order, and they have the same
+  ε-copy deserialization type of `Vec<T>`; full deserialization
+  restores the heap invariant;
+
+- sets are serialized as a sorted sequence, and they are ε-copy deserialized
+  as a [`SetView`] of the ε-copy deserialization type of `Vec<T>`, which
+  supports membership queries by binary search.
+
+*/
+
+use crate::prelude::*;
+use core::hash::{BuildHasher, Hash};
+use deser::*;
+use ser::*;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{
+    collections::{BTreeSet, BinaryHeap, VecDeque},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::{BTreeSet, BinaryHeap, HashSet, VecDeque};
+
+macro_rules! impl_seq_type_info {
+    ($ty:ident) => {
+        impl<T> CopyType for $ty<T> {
+            type Copy = Deep;
+        }
+
+        impl<T: TypeHash> TypeHash for $ty<T> {
+            fn type_hash(hasher: &mut impl core::hash::Hasher) {
+                stringify!($ty).hash(hasher);
+                T::type_hash(hasher);
+            }
+        }
+
+        impl<T: ReprHash> ReprHash for $ty<T> {
+            fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+                *offset_of = 0;
+                T::repr_hash(hasher, offset_of);
+            }
+        }
+    };
+}
+
+impl_seq_type_info!(VecDeque);
+impl_seq_type_info!(BinaryHeap);
+impl_seq_type_info!(BTreeSet);
+
+// VecDeque
+
+impl<T: CopyType + SerializeInner + TypeHash> SerializeInner for VecDeque<T>
+where
+    VecDeque<T>: SerializeHelper<<T as CopyType>::Copy>,
+{
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        SerializeHelper::_serialize_inner(self, backend)
+    }
+}
+
+impl<T: ZeroCopy + SerializeInner> SerializeHelper<Zero> for VecDeque<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        let (front, back) = self.as_slices();
+        serialize_chunks_zero(backend, self.len(), [front, back])
+    }
+}
+
+impl<T: DeepCopy + SerializeInner> SerializeHelper<Deep> for VecDeque<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_iter_deep(backend, self.iter())
+    }
+}
+
+impl<T: CopyType<Copy = Pool> + SerializeInner + AsRef<str>> SerializeHelper<Pool> for VecDeque<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_iter_pool(backend, self.iter())
+    }
+}
+
+impl<T> DeserializeInner for VecDeque<T>
+where
+    Vec<T>: DeserializeInner,
+{
+    type DeserType<'a> = <Vec<T> as DeserializeInner>::DeserType<'a>;
+    #[inline(always)]
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(Vec::<T>::_deserialize_full_inner(backend)?.into())
+    }
+
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        Vec::<T>::_deserialize_eps_inner(backend)
+    }
+}
+
+impl<T> DeserializeMutInner for VecDeque<T>
+where
+    Vec<T>: DeserializeMutInner,
+{
+    type DeserMutType<'a> = <Vec<T> as DeserializeMutInner>::DeserMutType<'a>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Vec::<T>::_deserialize_eps_mut_inner(backend)
+    }
+}
+
+// BinaryHeap
+
+impl<T: CopyType + SerializeInner + TypeHash> SerializeInner for BinaryHeap<T>
+where
+    BinaryHeap<T>: SerializeHelper<<T as CopyType>::Copy>,
+{
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        SerializeHelper::_serialize_inner(self, backend)
+    }
+}
+
+impl<T: ZeroCopy + SerializeInner> SerializeHelper<Zero> for BinaryHeap<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_slice_zero(backend, self.as_slice())
+    }
+}
+
+impl<T: DeepCopy + SerializeInner> SerializeHelper<Deep> for BinaryHeap<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_slice_deep(backend, self.as_slice())
+    }
+}
+
+impl<T: CopyType<Copy = Pool> + SerializeInner + AsRef<str>> SerializeHelper<Pool>
+    for BinaryHeap<T>
+{
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_iter_pool(backend, self.iter())
+    }
+}
+
+impl<T: Ord> DeserializeInner for BinaryHeap<T>
+where
+    Vec<T>: DeserializeInner,
+{
+    type DeserType<'a> = <Vec<T> as DeserializeInner>::DeserType<'a>;
+    #[inline(always)]
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(Vec::<T>::_deserialize_full_inner(backend)?.into())
+    }
+
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        Vec::<T>::_deserialize_eps_inner(backend)
+    }
+}
+
+impl<T: Ord> DeserializeMutInner for BinaryHeap<T>
+where
+    Vec<T>: DeserializeMutInner,
+{
+    type DeserMutType<'a> = <Vec<T> as DeserializeMutInner>::DeserMutType<'a>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Vec::<T>::_deserialize_eps_mut_inner(backend)
+    }
+}
+
+// BTreeSet
+
+impl<T: CopyType + SerializeInner + TypeHash> SerializeInner for BTreeSet<T>
+where
+    BTreeSet<T>: SerializeHelper<<T as CopyType>::Copy>,
+{
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        SerializeHelper::_serialize_inner(self, backend)
+    }
+}
+
+impl<T: ZeroCopy + SerializeInner> SerializeHelper<Zero> for BTreeSet<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_chunks_zero(backend, self.len(), self.iter().map(core::slice::from_ref))
+    }
+}
+
+impl<T: DeepCopy + SerializeInner> SerializeHelper<Deep> for BTreeSet<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_iter_deep(backend, self.iter())
+    }
+}
+
+impl<T: CopyType<Copy = Pool> + SerializeInner + AsRef<str>> SerializeHelper<Pool> for BTreeSet<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_iter_pool(backend, self.iter())
+    }
+}
+
+impl<T: Ord> DeserializeInner for BTreeSet<T>
+where
+    Vec<T>: DeserializeInner,
+{
+    type DeserType<'a> = SetView<<Vec<T> as DeserializeInner>::DeserType<'a>>;
+    #[inline(always)]
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(Vec::<T>::_deserialize_full_inner(backend)?
+            .into_iter()
+            .collect())
+    }
+
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        Ok(SetView::new(Vec::<T>::_deserialize_eps_inner(backend)?))
+    }
+}
+
+impl<T: Ord> DeserializeMutInner for BTreeSet<T>
+where
+    Vec<T>: DeserializeMutInner,
+{
+    type DeserMutType<'a> = SetView<<Vec<T> as DeserializeMutInner>::DeserMutType<'a>>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Ok(SetView::new(Vec::<T>::_deserialize_eps_mut_inner(backend)?))
+    }
+}
+
+// HashSet
+
+#[cfg(feature = "std")]
+impl<T, S> CopyType for HashSet<T, S> {
+    type Copy = Deep;
+}
+
+#[cfg(feature = "std")]
+impl<T: TypeHash, S> TypeHash for HashSet<T, S> {
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "HashSet".hash(hasher);
+        T::type_hash(hasher);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ReprHash, S> ReprHash for HashSet<T, S> {
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        *offset_of = 0;
+        T::repr_hash(hasher, offset_of);
+    }
+}
+
+/// Return the elements of a hash set in sorted order.
+#[cfg(feature = "std")]
+fn sorted<T: Ord, S>(set: &HashSet<T, S>) -> Vec<&T> {
+    let mut items: Vec<&T> = set.iter().collect();
+    items.sort_unstable();
+    items
+}
+
+#[cfg(feature = "std")]
+impl<T: CopyType + SerializeInner + TypeHash, S> SerializeInner for HashSet<T, S>
+where
+    HashSet<T, S>: SerializeHelper<<T as CopyType>::Copy>,
+{
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        SerializeHelper::_serialize_inner(self, backend)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: ZeroCopy + SerializeInner + Ord, S> SerializeHelper<Zero> for HashSet<T, S> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        let items = sorted(self);
+        serialize_chunks_zero(
+            backend,
+            items.len(),
+            items.into_iter().map(core::slice::from_ref),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: DeepCopy + SerializeInner + Ord, S> SerializeHelper<Deep> for HashSet<T, S> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_iter_deep(backend, sorted(self).into_iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: CopyType<Copy = Pool> + SerializeInner + AsRef<str> + Ord, S> SerializeHelper<Pool>
+    for HashSet<T, S>
+{
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_iter_pool(backend, sorted(self).into_iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash, S: BuildHasher + Default> DeserializeInner for HashSet<T, S>
+where
+    Vec<T>: DeserializeInner,
+{
+    type DeserType<'a> = SetView<<Vec<T> as DeserializeInner>::DeserType<'a>>;
+    #[inline(always)]
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(Vec::<T>::_deserialize_full_inner(backend)?
+            .into_iter()
+            .collect())
+    }
+
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        Ok(SetView::new(Vec::<T>::_deserialize_eps_inner(backend)?))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash, S: BuildHasher + Default> DeserializeMutInner for HashSet<T, S>
+where
+    Vec<T>: DeserializeMutInner,
+{
+    type DeserMutType<'a> = SetView<<Vec<T> as DeserializeMutInner>::DeserMutType<'a>>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Ok(SetView::new(Vec::<T>::_deserialize_eps_mut_inner(backend)?))
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Implementations for [`Cow`] slices and strings.
+
+`Cow<'_, [T]>`, where `T` is zero-copy, and `Cow<'_, str>` are serialized
+as vectors and strings, respectively. Full deserialization yields
+a [`Cow::Owned`], whereas ε-copy deserialization yields a [`Cow::Borrowed`]
+referring to the serialized data. Thus, a structure with [`Cow`] fields
+(and a lifetime parameter) is a single concrete type whether it was built in memory
+or ε-copy deserialized.
+
+*/
+
+use crate::deser::helpers::*;
+use crate::prelude::*;
+use core::hash::Hash;
+use deser::*;
+use ser::*;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::borrow::{Cow, ToOwned};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+
+impl<B: ?Sized + ToOwned> CopyType for Cow<'_, B> {
+    type Copy = Deep;
+}
+
+impl<B: ?Sized + ToOwned + TypeHash> TypeHash for Cow<'_, B> {
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "Cow".hash(hasher);
+        B::type_hash(hasher);
+    }
+}
+
+impl<T: Clone + ReprHash> ReprHash for Cow<'_, [T]> {
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        *offset_of = 0;
+        T::repr_hash(hasher, offset_of);
+    }
+}
+
+impl ReprHash for Cow<'_, str> {
+    fn repr_hash(_hasher: &mut impl core::hash::Hasher, _offset_of: &mut usize) {}
+}
+
+impl<T: ZeroCopy + SerializeInner + Clone> SerializeInner for Cow<'_, [T]> {
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_slice_zero(backend, self)
+    }
+}
+
+impl<T: ZeroCopy + DeserializeInner + Clone + 'static> DeserializeInner for Cow<'_, [T]> {
+    #[inline(always)]
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(Cow::Owned(deserialize_full_vec_zero(backend)?))
+    }
+    type DeserType<'a> = Cow<'a, [T]>;
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        Ok(Cow::Borrowed(deserialize_eps_slice_zero(backend)?))
+    }
+}
+
+impl<T: ZeroCopy + DeserializeInner + Clone + 'static> DeserializeMutInner for Cow<'_, [T]> {
+    type DeserMutType<'a> = Cow<'a, [T]>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Ok(Cow::Borrowed(deserialize_eps_mut_slice_zero(backend)?))
+    }
+}
+
+impl SerializeInner for Cow<'_, str> {
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_slice_zero(backend, self.as_bytes())
+    }
+}
+
+impl DeserializeInner for Cow<'_, str> {
+    #[inline(always)]
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(Cow::Owned(String::_deserialize_full_inner(backend)?))
+    }
+    type DeserType<'a> = Cow<'a, str>;
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        Ok(Cow::Borrowed(String::_deserialize_eps_inner(backend)?))
+    }
+}
+
+impl DeserializeMutInner for Cow<'_, str> {
+    type DeserMutType<'a> = Cow<'a, str>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Ok(Cow::Borrowed(String::_deserialize_eps_mut_inner(backend)?))
+    }
+}
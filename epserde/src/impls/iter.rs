@@ -0,0 +1,154 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Serialize-only implementation for iterators.
+
+[`SerIter`] wraps an iterator so that its items are serialized with the
+layout of a `Vec<T>`, without materializing the vector in memory. The result
+must be deserialized using `Vec<T>` as type: in particular, a
+`SerIter` can be used as the value of a type parameter of a structure whose
+serialized form must be deserialized with the parameter set to `Vec<T>`.
+
+If the length of the iterator is known in advance (i.e., its
+[size hint](Iterator::size_hint) is exact, as it happens for an
+[`ExactSizeIterator`]), items are written as they are produced.
+Otherwise, the length is back-patched once the iterator is exhausted,
+which is possible only if the data is serialized using
+[`Serialize::serialize_with_seek`].
+Chunked producers can be serialized by flattening them.
+
+```rust
+use epserde::prelude::*;
+use epserde::impls::iter::SerIter;
+use epserde::ser::SerializeOptions;
+
+let mut cursor = epserde::new_aligned_cursor();
+SerIter::new((0..1000_u64).map(|x| x * x)).serialize(&mut cursor).unwrap();
+let buf = cursor.into_inner();
+let s: &[u64] = <Vec<u64>>::deserialize_eps(&buf).unwrap();
+assert_eq!(s[10], 100);
+
+// The length of a filtered iterator is not known in advance
+let mut cursor = epserde::new_aligned_cursor();
+SerIter::new((0..1000_u64).filter(|x| x % 3 == 0))
+    .serialize_with_seek(&SerializeOptions::new(), &mut cursor)
+    .unwrap();
+let buf = cursor.into_inner();
+let s: &[u64] = <Vec<u64>>::deserialize_eps(&buf).unwrap();
+assert_eq!(s.len(), 334);
+```
+
+*/
+
+use crate::prelude::*;
+use core::cell::RefCell;
+use ser::*;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::vec::Vec;
+
+/// A wrapper for an iterator that serializes its items
+/// with the layout of a `Vec<T>`.
+///
+/// The iterator is consumed by serialization: serializing again the same
+/// wrapper will serialize the remaining items, if any.
+///
+/// Serialization fails with [`ser::Error::IteratorLengthMismatch`] if
+/// the iterator returns a number of items different from its exact
+/// [size hint](Iterator::size_hint), and with [`ser::Error::UnknownLength`]
+/// if the size hint is not exact and the backend cannot
+/// [back-patch](WriteWithPos::patch) the length.
+///
+/// Iterators on strings can be serialized only if their length is known
+/// in advance and they implement [`Clone`], as string pools are written
+/// in two passes.
+pub struct SerIter<I: Iterator>(RefCell<I>);
+
+impl<I: Iterator> SerIter<I> {
+    /// Wrap an iterator, or anything that can be turned into an iterator.
+    pub fn new(iter: impl IntoIterator<IntoIter = I>) -> Self {
+        Self(RefCell::new(iter.into_iter()))
+    }
+}
+
+impl<I: Iterator> From<I> for SerIter<I> {
+    fn from(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl<I: Iterator> CopyType for SerIter<I> {
+    type Copy = Deep;
+}
+
+impl<I: Iterator> TypeHash for SerIter<I>
+where
+    I::Item: TypeHash,
+{
+    #[inline(always)]
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        Vec::<I::Item>::type_hash(hasher);
+    }
+}
+
+impl<I: Iterator> ReprHash for SerIter<I>
+where
+    I::Item: ReprHash,
+{
+    #[inline(always)]
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        Vec::<I::Item>::repr_hash(hasher, offset_of);
+    }
+}
+
+impl<I: Iterator> SerializeInner for SerIter<I>
+where
+    I::Item: CopyType + SerializeInner + TypeHash,
+    SerIter<I>: SerializeHelper<<I::Item as CopyType>::Copy>,
+{
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        SerializeHelper::_serialize_inner(self, backend)
+    }
+}
+
+impl<I: Iterator> SerializeHelper<Zero> for SerIter<I>
+where
+    I::Item: ZeroCopy + SerializeInner,
+{
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_stream_zero(backend, &mut *self.0.borrow_mut())
+    }
+}
+
+impl<I: Iterator> SerializeHelper<Deep> for SerIter<I>
+where
+    I::Item: DeepCopy + SerializeInner,
+{
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_stream_deep(backend, &mut *self.0.borrow_mut())
+    }
+}
+
+impl<I: ExactSizeIterator + Clone> SerializeHelper<Pool> for SerIter<I>
+where
+    I::Item: CopyType<Copy = Pool> + SerializeInner + AsRef<str>,
+{
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        let mut iter = self.0.borrow_mut();
+        serialize_iter_pool(backend, iter.clone())?;
+        // Consume the iterator, as in the other cases
+        iter.by_ref().for_each(drop);
+        Ok(())
+    }
+}
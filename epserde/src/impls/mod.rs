@@ -13,10 +13,29 @@ and [`DeserializeInner`](crate::deser::DeserializeInner) for standard Rust types
 */
 
 pub mod array;
+#[cfg(feature = "arrayvec")]
+pub mod arrayvec;
+#[cfg(feature = "bitvec")]
+pub mod bitvec;
 pub mod boxed_slice;
+#[cfg(any(feature = "alloc", feature = "std"))]
+pub mod collections;
+#[cfg(any(feature = "alloc", feature = "std"))]
+pub mod cow;
+#[cfg(any(feature = "alloc", feature = "std"))]
+pub mod iter;
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+#[cfg(any(feature = "alloc", feature = "std"))]
+pub mod pointer;
 pub mod prim;
 pub mod slice;
+#[cfg(feature = "smallvec")]
+pub mod smallvec;
+pub mod stdlib;
 pub mod string;
+#[cfg(feature = "tinyvec")]
+pub mod tinyvec;
 pub mod tuple;
 #[cfg(any(feature = "alloc", feature = "std"))]
 pub mod vec;
@@ -0,0 +1,132 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Implementations for arrays of the [`ndarray`](::ndarray) crate
+(available with the `ndarray` feature).
+
+An [`Array<T, D>`] whose elements are zero-copy is serialized as its shape,
+its strides, and its elements in memory order, each with the layout of a
+`Vec`. Arrays are ε-copy deserialized as an [`ArrayView<'_, T, D>`] with the
+same shape and strides (arrays whose elements are not contiguous in memory are
+serialized in standard order).
+
+*/
+
+use crate::deser::helpers::*;
+use crate::prelude::*;
+use ::ndarray::{Array, ArrayView, ArrayViewMut, Dimension, ShapeBuilder};
+use core::hash::Hash;
+use deser::*;
+use ser::*;
+
+impl<T, D: Dimension> CopyType for Array<T, D> {
+    type Copy = Deep;
+}
+
+impl<T: TypeHash, D: Dimension> TypeHash for Array<T, D> {
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "ndarray::Array".hash(hasher);
+        D::NDIM.hash(hasher);
+        T::type_hash(hasher);
+    }
+}
+
+impl<T: ReprHash, D: Dimension> ReprHash for Array<T, D> {
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        *offset_of = 0;
+        T::repr_hash(hasher, offset_of);
+    }
+}
+
+impl<T: ZeroCopy + SerializeInner, D: Dimension> SerializeInner for Array<T, D> {
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_slice_zero(backend, self.shape())?;
+        match self.as_slice_memory_order() {
+            Some(data) => {
+                serialize_slice_zero(backend, self.strides())?;
+                serialize_slice_zero(backend, data)
+            }
+            None => {
+                // Elements are written in standard order
+                let strides = self.raw_dim().default_strides();
+                let strides = strides
+                    .slice()
+                    .iter()
+                    .map(|&s| s as isize)
+                    .collect::<Vec<_>>();
+                serialize_slice_zero(backend, strides.as_slice())?;
+                let elements = self.iter().collect::<Vec<_>>();
+                serialize_chunks_zero(
+                    backend,
+                    elements.len(),
+                    elements.into_iter().map(core::slice::from_ref),
+                )
+            }
+        }
+    }
+}
+
+/// Build the dimension and the strides of an array from their serialized
+/// representation.
+fn shape<D: Dimension>(shape: &[usize], strides: &[isize]) -> deser::Result<(D, D)> {
+    if D::NDIM.is_some_and(|ndim| ndim != shape.len()) || strides.len() != shape.len() {
+        return Err(deser::Error::InvalidValue("ndarray::Array"));
+    }
+    let mut dim = D::zeros(shape.len());
+    dim.slice_mut().copy_from_slice(shape);
+    let mut dim_strides = D::zeros(strides.len());
+    // Strides are stored as usize in ndarray dimensions
+    for (d, &s) in dim_strides.slice_mut().iter_mut().zip(strides) {
+        *d = s as usize;
+    }
+    Ok((dim, dim_strides))
+}
+
+impl<T: ZeroCopy + DeserializeInner + 'static, D: Dimension + 'static> DeserializeInner
+    for Array<T, D>
+{
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        let dim = deserialize_full_vec_zero::<usize>(backend)?;
+        let strides = deserialize_full_vec_zero::<isize>(backend)?;
+        let data = deserialize_full_vec_zero::<T>(backend)?;
+        let (dim, strides) = shape::<D>(&dim, &strides)?;
+        Array::from_shape_vec(dim.strides(strides), data)
+            .map_err(|_| deser::Error::InvalidValue("ndarray::Array"))
+    }
+    type DeserType<'a> = ArrayView<'a, T, D>;
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        let dim = deserialize_eps_slice_zero::<usize>(backend)?;
+        let strides = deserialize_eps_slice_zero::<isize>(backend)?;
+        let data = deserialize_eps_slice_zero::<T>(backend)?;
+        let (dim, strides) = shape::<D>(dim, strides)?;
+        ArrayView::from_shape(dim.strides(strides), data)
+            .map_err(|_| deser::Error::InvalidValue("ndarray::Array"))
+    }
+}
+
+impl<T: ZeroCopy + DeserializeInner + 'static, D: Dimension + 'static> DeserializeMutInner
+    for Array<T, D>
+{
+    type DeserMutType<'a> = ArrayViewMut<'a, T, D>;
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        let dim = deserialize_eps_mut_slice_zero::<usize>(backend)?;
+        let strides = deserialize_eps_mut_slice_zero::<isize>(backend)?;
+        let data = deserialize_eps_mut_slice_zero::<T>(backend)?;
+        let (dim, strides) = shape::<D>(dim, strides)?;
+        ArrayViewMut::from_shape(dim.strides(strides), data)
+            .map_err(|_| deser::Error::InvalidValue("ndarray::Array"))
+    }
+}
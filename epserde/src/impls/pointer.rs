@@ -0,0 +1,86 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Implementations for smart pointers: [`Box`], [`Rc`] and [`Arc`].
+
+Smart pointers are serialized transparently as their content: type and
+representation hashes are those of the content, so, for example, a
+`Box<T>` can be deserialized as a `T` and vice versa. The deserialization
+type is the deserialization type of the content, whereas
+full deserialization rebuilds the smart pointer.
+
+*/
+
+use crate::prelude::*;
+use deser::*;
+use ser::*;
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+#[cfg(feature = "std")]
+use std::{rc::Rc, sync::Arc};
+
+macro_rules! impl_pointer {
+    ($($ty:ident),*) => {$(
+        impl<T> CopyType for $ty<T> {
+            type Copy = Deep;
+        }
+
+        impl<T: TypeHash> TypeHash for $ty<T> {
+            #[inline(always)]
+            fn type_hash(hasher: &mut impl core::hash::Hasher) {
+                T::type_hash(hasher);
+            }
+        }
+
+        impl<T: ReprHash> ReprHash for $ty<T> {
+            #[inline(always)]
+            fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+                T::repr_hash(hasher, offset_of);
+            }
+        }
+
+        impl<T: SerializeInner> SerializeInner for $ty<T> {
+            // A pointer is never zero-copy, even if its content is.
+            const IS_ZERO_COPY: bool = false;
+            const ZERO_COPY_MISMATCH: bool = false;
+
+            #[inline(always)]
+            fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+                <T as SerializeInner>::_serialize_inner(self, backend)
+            }
+        }
+
+        impl<T: DeserializeInner> DeserializeInner for $ty<T> {
+            #[inline(always)]
+            fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+                Ok($ty::new(T::_deserialize_full_inner(backend)?))
+            }
+            type DeserType<'a> = <T as DeserializeInner>::DeserType<'a>;
+            #[inline(always)]
+            fn _deserialize_eps_inner<'a>(
+                backend: &mut SliceWithPos<'a>,
+            ) -> deser::Result<Self::DeserType<'a>> {
+                T::_deserialize_eps_inner(backend)
+            }
+        }
+
+        impl<T: DeserializeMutInner> DeserializeMutInner for $ty<T> {
+            type DeserMutType<'a> = <T as DeserializeMutInner>::DeserMutType<'a>;
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner<'a>(
+                backend: &mut SliceWithPosMut<'a>,
+            ) -> deser::Result<Self::DeserMutType<'a>> {
+                T::_deserialize_eps_mut_inner(backend)
+            }
+        }
+    )*};
+}
+
+impl_pointer!(Box, Rc, Arc);
@@ -7,7 +7,11 @@
 
 /*!
 
-Implementations for primitive types, `()`, [`PhantomData`] and [`Option`].
+Implementations for primitive types, [non-zero integer types](core::num::NonZero),
+atomic types, `()`, [`PhantomData`] and [`Option`].
+
+With the `half` feature, the half-precision floating-point types `half::f16` and
+`half::bf16` are zero-copy primitive types, too.
 
 */
 
@@ -15,13 +19,25 @@ use crate::prelude::*;
 use core::hash::Hash;
 use core::marker::PhantomData;
 use core::mem::size_of;
+use core::num::*;
+use core::sync::atomic::*;
+// Not core::sync::atomic::Atomic
+use crate::traits::Atomic;
 use deser::*;
 use ser::*;
 
 macro_rules! impl_prim_type_hash {
-    ($($ty:ty),*) => {$(
+    (Zero, $option_copy:ident; $($ty:ty),*) => {
+        impl_prim_type_hash!(Zero; $($ty),*);
+        $(
+            impl ZeroOptionCopyType for $ty {
+                type OptionCopy = $option_copy;
+            }
+        )*
+    };
+    ($copy:ident; $($ty:ty),*) => {$(
         impl CopyType for $ty {
-            type Copy = Zero;
+            type Copy = $copy;
         }
 
         impl TypeHash for $ty {
@@ -44,6 +60,9 @@ macro_rules! impl_prim_type_hash {
             }
         }
     )*};
+    ($($ty:ty),*) => {
+        impl_prim_type_hash!(Zero, Deep; $($ty),*);
+    };
 }
 
 macro_rules! impl_prim_ser_des {
@@ -82,6 +101,16 @@ macro_rules! impl_prim_ser_des {
                 Ok(res)
             }
         }
+
+        impl DeserializeMutInner for $ty {
+            type DeserMutType<'a> = Self;
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner<'a>(
+                backend: &mut SliceWithPosMut<'a>,
+            ) -> deser::Result<Self::DeserMutType<'a>> {
+                Self::_deserialize_full_inner(backend)
+            }
+        }
     )*};
 }
 
@@ -106,6 +135,13 @@ impl_prim_type_hash!(
 );
 impl_prim_ser_des!(isize, i8, i16, i32, i64, i128, usize, u8, u16, u32, u64, u128, f32, f64);
 
+#[cfg(feature = "half")]
+use half::{bf16, f16};
+#[cfg(feature = "half")]
+impl_prim_type_hash!(f16, bf16);
+#[cfg(feature = "half")]
+impl_prim_ser_des!(f16, bf16);
+
 // Booleans are zero-copy serialized as u8.
 
 impl SerializeInner for bool {
@@ -120,6 +156,9 @@ impl SerializeInner for bool {
 }
 
 impl DeserializeInner for bool {
+    // Only 0 and 1 are valid values of a bool
+    const MUST_CHECK_ZERO_COPY: bool = true;
+
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<bool> {
         Ok(u8::_deserialize_full_inner(backend)? != 0)
@@ -133,6 +172,24 @@ impl DeserializeInner for bool {
         backend.skip(1);
         Ok(res)
     }
+
+    #[inline(always)]
+    fn _check_zero_copy(bytes: &[u8]) -> deser::Result<()> {
+        if bytes[0] > 1 {
+            return Err(deser::Error::InvalidValue("bool"));
+        }
+        Ok(())
+    }
+}
+
+impl DeserializeMutInner for bool {
+    type DeserMutType<'a> = Self;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Self::_deserialize_full_inner(backend)
+    }
 }
 
 // Chars are zero-copy serialized as u32.
@@ -148,19 +205,213 @@ impl SerializeInner for char {
 }
 
 impl DeserializeInner for char {
+    // Surrogates and values above 0x10FFFF are not valid chars
+    const MUST_CHECK_ZERO_COPY: bool = true;
+
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-        Ok(char::from_u32(u32::_deserialize_full_inner(backend)?).unwrap())
+        char::from_u32(u32::_deserialize_full_inner(backend)?)
+            .ok_or(deser::Error::InvalidValue("char"))
     }
     type DeserType<'a> = Self;
     #[inline(always)]
     fn _deserialize_eps_inner<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
-        Ok(char::from_u32(u32::_deserialize_eps_inner(backend)?).unwrap())
+        char::from_u32(u32::_deserialize_eps_inner(backend)?)
+            .ok_or(deser::Error::InvalidValue("char"))
+    }
+
+    #[inline(always)]
+    fn _check_zero_copy(bytes: &[u8]) -> deser::Result<()> {
+        char::from_u32(u32::from_ne_bytes(bytes.try_into().unwrap()))
+            .ok_or(deser::Error::InvalidValue("char"))
+            .map(|_| ())
+    }
+}
+
+impl DeserializeMutInner for char {
+    type DeserMutType<'a> = Self;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Self::_deserialize_full_inner(backend)
     }
 }
 
+// Non-zero integer types are zero-copy, and are serialized using the
+// underlying primitive type, as they have the same in-memory representation.
+// Since zero is not a valid value, they are checked when deserializing.
+
+macro_rules! impl_nonzero_ser_des {
+    ($($ty:ty => $prim:ty),*) => {$(
+        impl SerializeInner for $ty {
+            const IS_ZERO_COPY: bool = true;
+            const ZERO_COPY_MISMATCH: bool = false;
+
+            #[inline(always)]
+            fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+                self.get()._serialize_inner(backend)
+            }
+        }
+
+        impl DeserializeInner for $ty {
+            const MUST_CHECK_ZERO_COPY: bool = true;
+            // Zero is the only invalid value, and it represents None
+            const MUST_CHECK_ZERO_COPY_OPTION: bool = false;
+
+            #[inline(always)]
+            fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<$ty> {
+                <$ty>::new(<$prim>::_deserialize_full_inner(backend)?)
+                    .ok_or(deser::Error::InvalidValue(stringify!($ty)))
+            }
+            type DeserType<'a> = Self;
+            #[inline(always)]
+            fn _deserialize_eps_inner<'a>(
+                backend: &mut SliceWithPos<'a>,
+            ) -> deser::Result<Self::DeserType<'a>> {
+                <$ty>::new(<$prim>::_deserialize_eps_inner(backend)?)
+                    .ok_or(deser::Error::InvalidValue(stringify!($ty)))
+            }
+
+            #[inline(always)]
+            fn _check_zero_copy(bytes: &[u8]) -> deser::Result<()> {
+                if bytes.iter().all(|&b| b == 0) {
+                    return Err(deser::Error::InvalidValue(stringify!($ty)));
+                }
+                Ok(())
+            }
+        }
+
+        impl DeserializeMutInner for $ty {
+            type DeserMutType<'a> = Self;
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner<'a>(
+                backend: &mut SliceWithPosMut<'a>,
+            ) -> deser::Result<Self::DeserMutType<'a>> {
+                Self::_deserialize_full_inner(backend)
+            }
+        }
+    )*};
+}
+
+// Options of non-zero integer types are zero-copy, as Rust guarantees
+// that None is represented by zero.
+impl_prim_type_hash!(
+    Zero, Zero;
+    NonZeroIsize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroUsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128
+);
+impl_nonzero_ser_des!(
+    NonZeroIsize => isize,
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroUsize => usize,
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128
+);
+
+// Atomic types are serialized using the underlying primitive type, as they
+// have the same in-memory representation. They are not zero-copy, as
+// modifying them through a shared reference to read-only memory would
+// be unsound: their selector is Atomic (see AtomicCopy).
+
+macro_rules! impl_atomic_ser_des {
+    ($($ty:ty => $prim:ty),*) => {$(
+        // SAFETY: atomic types have the same size and representation of
+        // the underlying primitive type, and alignment equal to their size.
+        unsafe impl AtomicCopy for $ty {
+            type NonAtomic = $prim;
+
+            #[inline(always)]
+            fn new(value: $prim) -> Self {
+                <$ty>::new(value)
+            }
+
+            #[inline(always)]
+            fn load_relaxed(&self) -> $prim {
+                self.load(Ordering::Relaxed)
+            }
+        }
+
+        impl SerializeInner for $ty {
+            const IS_ZERO_COPY: bool = false;
+            const ZERO_COPY_MISMATCH: bool = false;
+
+            #[inline(always)]
+            fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+                self.load(Ordering::Relaxed)._serialize_inner(backend)
+            }
+        }
+
+        impl DeserializeInner for $ty {
+            #[inline(always)]
+            fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<$ty> {
+                Ok(<$ty>::new(<$prim>::_deserialize_full_inner(backend)?))
+            }
+            type DeserType<'a> = Self;
+            #[inline(always)]
+            fn _deserialize_eps_inner<'a>(
+                backend: &mut SliceWithPos<'a>,
+            ) -> deser::Result<Self::DeserType<'a>> {
+                Ok(<$ty>::new(<$prim>::_deserialize_eps_inner(backend)?))
+            }
+        }
+
+        impl DeserializeMutInner for $ty {
+            type DeserMutType<'a> = Self;
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner<'a>(
+                backend: &mut SliceWithPosMut<'a>,
+            ) -> deser::Result<Self::DeserMutType<'a>> {
+                Self::_deserialize_full_inner(backend)
+            }
+        }
+    )*};
+}
+
+#[cfg(target_has_atomic = "8")]
+impl_prim_type_hash!(Atomic; AtomicBool, AtomicI8, AtomicU8);
+#[cfg(target_has_atomic = "8")]
+impl_atomic_ser_des!(AtomicBool => bool, AtomicI8 => i8, AtomicU8 => u8);
+
+#[cfg(target_has_atomic = "16")]
+impl_prim_type_hash!(Atomic; AtomicI16, AtomicU16);
+#[cfg(target_has_atomic = "16")]
+impl_atomic_ser_des!(AtomicI16 => i16, AtomicU16 => u16);
+
+#[cfg(target_has_atomic = "32")]
+impl_prim_type_hash!(Atomic; AtomicI32, AtomicU32);
+#[cfg(target_has_atomic = "32")]
+impl_atomic_ser_des!(AtomicI32 => i32, AtomicU32 => u32);
+
+#[cfg(target_has_atomic = "64")]
+impl_prim_type_hash!(Atomic; AtomicI64, AtomicU64);
+#[cfg(target_has_atomic = "64")]
+impl_atomic_ser_des!(AtomicI64 => i64, AtomicU64 => u64);
+
+#[cfg(target_has_atomic = "ptr")]
+impl_prim_type_hash!(Atomic; AtomicIsize, AtomicUsize);
+#[cfg(target_has_atomic = "ptr")]
+impl_atomic_ser_des!(AtomicIsize => isize, AtomicUsize => usize);
+
 // () is zero-copy. No reading or writing is performed when (de)serializing it.
 
 impl SerializeInner for () {
@@ -187,12 +438,26 @@ impl DeserializeInner for () {
     }
 }
 
+impl DeserializeMutInner for () {
+    type DeserMutType<'a> = Self;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        _backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Ok(())
+    }
+}
+
 // PhantomData is zero-copy. No reading or writing is performed when (de)serializing it.
 
 impl<T: ?Sized> CopyType for PhantomData<T> {
     type Copy = Zero;
 }
 
+impl<T: ?Sized> ZeroOptionCopyType for PhantomData<T> {
+    type OptionCopy = Deep;
+}
+
 impl<T: ?Sized + TypeHash> TypeHash for PhantomData<T> {
     #[inline(always)]
     fn type_hash(hasher: &mut impl core::hash::Hasher) {
@@ -230,10 +495,27 @@ impl<T: ?Sized + TypeHash> DeserializeInner for PhantomData<T> {
     }
 }
 
-// Options are deep-copy types serialized as a one-byte tag (0 for None, 1 for Some) followed, in case, by the value.
+impl<T: ?Sized + TypeHash> DeserializeMutInner for PhantomData<T> {
+    type DeserMutType<'a> = Self;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        _backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Ok(PhantomData)
+    }
+}
 
-impl<T> CopyType for Option<T> {
-    type Copy = Deep;
+// Options are deep-copy types serialized as a one-byte tag (0 for None, 1 for Some)
+// followed, in case, by the value, unless `T::OptionCopy` is `Zero`: in this case,
+// they are zero-copy types with the same representation of `T`, and None is
+// represented by zero bytes.
+
+impl<T: OptionCopyType> CopyType for Option<T> {
+    type Copy = T::OptionCopy;
+}
+
+impl<T: OptionCopyType<OptionCopy = Zero>> ZeroOptionCopyType for Option<T> {
+    type OptionCopy = Deep;
 }
 
 impl<T: TypeHash> TypeHash for Option<T> {
@@ -244,17 +526,74 @@ impl<T: TypeHash> TypeHash for Option<T> {
     }
 }
 
-impl<T: ReprHash> ReprHash for Option<T> {
+impl<T: OptionCopyType + ReprHash> ReprHash for Option<T> {
     fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
-        *offset_of = 0;
+        if !<T::OptionCopy as CopySelector>::IS_ZERO_COPY {
+            *offset_of = 0;
+        }
         T::repr_hash(hasher, offset_of);
     }
 }
 
-impl<T: SerializeInner> SerializeInner for Option<T> {
-    const IS_ZERO_COPY: bool = false;
+impl<T: MaxSizeOf> MaxSizeOf for Option<T> {
+    #[inline(always)]
+    fn max_size_of() -> usize {
+        T::max_size_of()
+    }
+}
+
+/// Compile-time check that `Option<T>` has the same size of `T` and
+/// that `None` is represented by zero bytes.
+///
+/// Note that if the representation of `None` contains uninitialized bytes
+/// the check fails, too, as uninitialized bytes cannot be read at compile time.
+struct NoneIsZero<T>(PhantomData<T>);
+
+impl<T> NoneIsZero<T> {
+    const CHECK: () = {
+        assert!(
+            size_of::<Option<T>>() == size_of::<T>(),
+            "Option<T> is declared zero-copy, but it is larger than T"
+        );
+        let none = core::mem::ManuallyDrop::new(None::<T>);
+        let ptr = &none as *const core::mem::ManuallyDrop<Option<T>> as *const u8;
+        let mut i = 0;
+        while i < size_of::<T>() {
+            // SAFETY: we are reading the bytes of a local variable
+            assert!(
+                unsafe { *ptr.add(i) } == 0,
+                "Option<T> is declared zero-copy, but None is not represented by zero bytes"
+            );
+            i += 1;
+        }
+    };
+}
+
+impl<T: OptionCopyType + SerializeInner> SerializeInner for Option<T>
+where
+    Option<T>: SerializeHelper<<T as OptionCopyType>::OptionCopy>,
+{
+    const IS_ZERO_COPY: bool = <T::OptionCopy as CopySelector>::IS_ZERO_COPY;
     const ZERO_COPY_MISMATCH: bool = false;
 
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        SerializeHelper::_serialize_inner(self, backend)
+    }
+}
+
+impl<T: OptionCopyType<OptionCopy = Zero> + SerializeInner + Copy + MaxSizeOf> SerializeHelper<Zero>
+    for Option<T>
+{
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        #[allow(clippy::let_unit_value)]
+        let _ = NoneIsZero::<T>::CHECK;
+        serialize_zero(backend, self)
+    }
+}
+
+impl<T: OptionCopyType<OptionCopy = Deep> + SerializeInner> SerializeHelper<Deep> for Option<T> {
     #[inline(always)]
     fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
         match self {
@@ -267,9 +606,70 @@ impl<T: SerializeInner> SerializeInner for Option<T> {
     }
 }
 
-impl<T: DeserializeInner> DeserializeInner for Option<T> {
+impl<T: OptionCopyType + DeserializeInner> DeserializeInner for Option<T>
+where
+    Option<T>: DeserializeHelper<<T as OptionCopyType>::OptionCopy, FullType = Option<T>>,
+{
+    const MUST_CHECK_ZERO_COPY: bool = T::MUST_CHECK_ZERO_COPY_OPTION;
+    type DeserType<'a> =
+        <Option<T> as DeserializeHelper<<T as OptionCopyType>::OptionCopy>>::DeserType<'a>;
+
     #[inline(always)]
     fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        <Option<T> as DeserializeHelper<<T as OptionCopyType>::OptionCopy>>::_deserialize_full_inner_impl(
+            backend,
+        )
+    }
+
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        <Option<T> as DeserializeHelper<<T as OptionCopyType>::OptionCopy>>::_deserialize_eps_inner_impl(
+            backend,
+        )
+    }
+
+    #[inline(always)]
+    fn _check_zero_copy(bytes: &[u8]) -> deser::Result<()> {
+        // Zero bytes represent None
+        if bytes.iter().all(|&b| b == 0) {
+            return Ok(());
+        }
+        T::_check_zero_copy(bytes)
+    }
+}
+
+// Zero-copy options are always fully deserialized, as primitive types.
+impl<T: OptionCopyType<OptionCopy = Zero> + DeserializeInner + Copy + MaxSizeOf>
+    DeserializeHelper<Zero> for Option<T>
+{
+    type FullType = Self;
+    type DeserType<'a> = Self;
+
+    #[inline(always)]
+    fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        #[allow(clippy::let_unit_value)]
+        let _ = NoneIsZero::<T>::CHECK;
+        deserialize_full_zero::<Self>(backend)
+    }
+
+    #[inline(always)]
+    fn _deserialize_eps_inner_impl<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        Self::_deserialize_full_inner_impl(backend)
+    }
+}
+
+impl<T: OptionCopyType<OptionCopy = Deep> + DeserializeInner> DeserializeHelper<Deep>
+    for Option<T>
+{
+    type FullType = Self;
+    type DeserType<'a> = Option<<T as DeserializeInner>::DeserType<'a>>;
+
+    #[inline(always)]
+    fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
         let tag = u8::_deserialize_full_inner(backend)?;
         match tag {
             0 => Ok(None),
@@ -277,16 +677,65 @@ impl<T: DeserializeInner> DeserializeInner for Option<T> {
             _ => Err(deser::Error::InvalidTag(tag as usize)),
         }
     }
-    type DeserType<'a> = Option<<T as DeserializeInner>::DeserType<'a>>;
+
     #[inline(always)]
-    fn _deserialize_eps_inner<'a>(
+    fn _deserialize_eps_inner_impl<'a>(
         backend: &mut SliceWithPos<'a>,
     ) -> deser::Result<Self::DeserType<'a>> {
         let tag = u8::_deserialize_full_inner(backend)?;
         match tag {
             0 => Ok(None),
             1 => Ok(Some(T::_deserialize_eps_inner(backend)?)),
-            _ => Err(deser::Error::InvalidTag(backend.data[0] as usize)),
+            _ => Err(deser::Error::InvalidTag(tag as usize)),
+        }
+    }
+}
+
+impl<T: OptionCopyType + DeserializeMutInner> DeserializeMutInner for Option<T>
+where
+    Option<T>: DeserializeHelper<<T as OptionCopyType>::OptionCopy, FullType = Option<T>>,
+    Option<T>: DeserializeMutHelper<<T as OptionCopyType>::OptionCopy>,
+{
+    type DeserMutType<'a> =
+        <Option<T> as DeserializeMutHelper<<T as OptionCopyType>::OptionCopy>>::DeserMutType<'a>;
+
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        <Option<T> as DeserializeMutHelper<<T as OptionCopyType>::OptionCopy>>::_deserialize_eps_mut_inner_impl(
+            backend,
+        )
+    }
+}
+
+impl<T: OptionCopyType<OptionCopy = Zero> + DeserializeInner + Copy + MaxSizeOf>
+    DeserializeMutHelper<Zero> for Option<T>
+{
+    type DeserMutType<'a> = Self;
+
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        <Self as DeserializeHelper<Zero>>::_deserialize_full_inner_impl(backend)
+    }
+}
+
+impl<T: OptionCopyType<OptionCopy = Deep> + DeserializeMutInner> DeserializeMutHelper<Deep>
+    for Option<T>
+{
+    type DeserMutType<'a> = Option<<T as DeserializeMutInner>::DeserMutType<'a>>;
+
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        let tag = u8::_deserialize_full_inner(backend)?;
+        match tag {
+            0 => Ok(None),
+            1 => Ok(Some(T::_deserialize_eps_mut_inner(backend)?)),
+            _ => Err(deser::Error::InvalidTag(tag as usize)),
         }
     }
 }
@@ -0,0 +1,44 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Implementations for [`SmallVec`] (available with the `smallvec` feature).
+
+Small vectors are serialized with the layout of a `Vec<T>`, and they have the
+same ε-copy deserialization type of `Vec<T>` (e.g., a slice if `T` is
+zero-copy). Full deserialization returns a small vector, which is inline
+if the elements fit its inline capacity.
+
+*/
+
+use crate::impls::vec::impl_vec_layout;
+use crate::prelude::*;
+use ::smallvec::{Array, SmallVec};
+use core::hash::Hash;
+
+impl<A: Array> TypeHash for SmallVec<A>
+where
+    A::Item: TypeHash,
+{
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "SmallVec".hash(hasher);
+        A::Item::type_hash(hasher);
+    }
+}
+
+impl<A: Array> ReprHash for SmallVec<A>
+where
+    A::Item: ReprHash,
+{
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        *offset_of = 0;
+        A::Item::repr_hash(hasher, offset_of);
+    }
+}
+
+impl_vec_layout!([A: Array] SmallVec<A>, A::Item, |v| Ok(SmallVec::from_vec(v)));
@@ -0,0 +1,427 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Implementations for value types of the standard library:
+[`Wrapping`], [`Reverse`], [`Ordering`](core::cmp::Ordering),
+[`Duration`], [`Range`], [`RangeInclusive`] and [`Result`](core::result::Result).
+
+*/
+
+use crate::prelude::*;
+use core::cmp::Reverse;
+use core::hash::Hash;
+use core::num::Wrapping;
+use core::ops::{Range, RangeInclusive};
+use core::time::Duration;
+use deser::*;
+use ser::*;
+
+// Wrapping and Reverse are `repr(transparent)`, so they are (de)serialized
+// exactly as the wrapped type, and they are zero-copy if the wrapped type is.
+// The deserialization type wraps the deserialization type of the wrapped type.
+
+macro_rules! impl_transparent {
+    ($($ty:ident),*) => {$(
+        impl<T: CopyType> CopyType for $ty<T> {
+            // Normalize the selector to Zero or Deep, as wrapped
+            // strings are not pooled
+            type Copy = <T::Copy as CopySelector>::And<Zero>;
+        }
+
+        impl<T: ZeroOptionCopyType> ZeroOptionCopyType for $ty<T> {
+            // Option<T> is zero-copy if T is, as we are repr(transparent)
+            type OptionCopy = <T as ZeroOptionCopyType>::OptionCopy;
+        }
+
+        impl<T: TypeHash> TypeHash for $ty<T> {
+            #[inline(always)]
+            fn type_hash(hasher: &mut impl core::hash::Hasher) {
+                stringify!($ty).hash(hasher);
+                T::type_hash(hasher);
+            }
+        }
+
+        impl<T: ReprHash> ReprHash for $ty<T> {
+            #[inline(always)]
+            fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+                T::repr_hash(hasher, offset_of);
+            }
+        }
+
+        impl<T: MaxSizeOf> MaxSizeOf for $ty<T> {
+            #[inline(always)]
+            fn max_size_of() -> usize {
+                T::max_size_of()
+            }
+        }
+
+        impl<T: SerializeInner> SerializeInner for $ty<T> {
+            const IS_ZERO_COPY: bool = T::IS_ZERO_COPY;
+            const ZERO_COPY_MISMATCH: bool = T::ZERO_COPY_MISMATCH;
+
+            #[inline(always)]
+            fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+                self.0._serialize_inner(backend)
+            }
+        }
+
+        impl<T: DeserializeInner> DeserializeInner for $ty<T> {
+            const MUST_CHECK_ZERO_COPY: bool = T::MUST_CHECK_ZERO_COPY;
+            const MUST_CHECK_ZERO_COPY_OPTION: bool = T::MUST_CHECK_ZERO_COPY_OPTION;
+
+            #[inline(always)]
+            fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+                Ok($ty(T::_deserialize_full_inner(backend)?))
+            }
+            type DeserType<'a> = $ty<<T as DeserializeInner>::DeserType<'a>>;
+            #[inline(always)]
+            fn _deserialize_eps_inner<'a>(
+                backend: &mut SliceWithPos<'a>,
+            ) -> deser::Result<Self::DeserType<'a>> {
+                Ok($ty(T::_deserialize_eps_inner(backend)?))
+            }
+
+            #[inline(always)]
+            fn _check_zero_copy(bytes: &[u8]) -> deser::Result<()> {
+                T::_check_zero_copy(bytes)
+            }
+        }
+
+        impl<T: DeserializeMutInner> DeserializeMutInner for $ty<T> {
+            type DeserMutType<'a> = $ty<<T as DeserializeMutInner>::DeserMutType<'a>>;
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner<'a>(
+                backend: &mut SliceWithPosMut<'a>,
+            ) -> deser::Result<Self::DeserMutType<'a>> {
+                Ok($ty(T::_deserialize_eps_mut_inner(backend)?))
+            }
+        }
+    )*};
+}
+
+impl_transparent!(Wrapping, Reverse);
+
+// Orderings are zero-copy, and are serialized as an i8 (-1, 0 or 1),
+// which is their in-memory representation.
+
+impl CopyType for core::cmp::Ordering {
+    type Copy = Zero;
+}
+
+impl ZeroOptionCopyType for core::cmp::Ordering {
+    type OptionCopy = Deep;
+}
+
+impl TypeHash for core::cmp::Ordering {
+    #[inline(always)]
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "Ordering".hash(hasher);
+    }
+}
+
+impl ReprHash for core::cmp::Ordering {
+    #[inline(always)]
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        crate::traits::std_repr_hash::<Self>(hasher, offset_of)
+    }
+}
+
+impl MaxSizeOf for core::cmp::Ordering {
+    #[inline(always)]
+    fn max_size_of() -> usize {
+        core::mem::size_of::<Self>()
+    }
+}
+
+impl SerializeInner for core::cmp::Ordering {
+    const IS_ZERO_COPY: bool = true;
+    const ZERO_COPY_MISMATCH: bool = false;
+
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        (*self as i8)._serialize_inner(backend)
+    }
+}
+
+fn ordering_from_i8(value: i8) -> deser::Result<core::cmp::Ordering> {
+    match value {
+        -1 => Ok(core::cmp::Ordering::Less),
+        0 => Ok(core::cmp::Ordering::Equal),
+        1 => Ok(core::cmp::Ordering::Greater),
+        _ => Err(deser::Error::InvalidValue("Ordering")),
+    }
+}
+
+impl DeserializeInner for core::cmp::Ordering {
+    const MUST_CHECK_ZERO_COPY: bool = true;
+
+    #[inline(always)]
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        ordering_from_i8(i8::_deserialize_full_inner(backend)?)
+    }
+    type DeserType<'a> = Self;
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        ordering_from_i8(i8::_deserialize_eps_inner(backend)?)
+    }
+
+    #[inline(always)]
+    fn _check_zero_copy(bytes: &[u8]) -> deser::Result<()> {
+        ordering_from_i8(bytes[0] as i8).map(|_| ())
+    }
+}
+
+impl DeserializeMutInner for core::cmp::Ordering {
+    type DeserMutType<'a> = Self;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Self::_deserialize_full_inner(backend)
+    }
+}
+
+// Durations are deep-copy, as their in-memory representation is not specified,
+// and are serialized as seconds (u64) followed by nanoseconds (u32).
+
+impl CopyType for Duration {
+    type Copy = Deep;
+}
+
+impl TypeHash for Duration {
+    #[inline(always)]
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "Duration".hash(hasher);
+    }
+}
+
+impl ReprHash for Duration {
+    #[inline(always)]
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        *offset_of = 0;
+        u64::repr_hash(hasher, offset_of);
+        *offset_of = 0;
+        u32::repr_hash(hasher, offset_of);
+    }
+}
+
+impl SerializeInner for Duration {
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        backend.write("secs", &self.as_secs())?;
+        backend.write("nanos", &self.subsec_nanos())
+    }
+}
+
+impl DeserializeInner for Duration {
+    #[inline(always)]
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        let secs = u64::_deserialize_full_inner(backend)?;
+        let nanos = u32::_deserialize_full_inner(backend)?;
+        if nanos >= 1_000_000_000 {
+            return Err(deser::Error::InvalidValue("Duration"));
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+    type DeserType<'a> = Self;
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        Self::_deserialize_full_inner(backend)
+    }
+}
+
+impl DeserializeMutInner for Duration {
+    type DeserMutType<'a> = Self;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        Self::_deserialize_full_inner(backend)
+    }
+}
+
+// Ranges are deep-copy, as their in-memory representation is not specified,
+// and are serialized as their start followed by their end. Note that
+// the exhaustion state of a RangeInclusive is not serialized.
+
+macro_rules! impl_range {
+    ($ty:ident, $new:expr, |$this:ident| ($start:expr, $end:expr)) => {
+        impl<T> CopyType for $ty<T> {
+            type Copy = Deep;
+        }
+
+        impl<T: TypeHash> TypeHash for $ty<T> {
+            #[inline(always)]
+            fn type_hash(hasher: &mut impl core::hash::Hasher) {
+                stringify!($ty).hash(hasher);
+                T::type_hash(hasher);
+            }
+        }
+
+        impl<T: ReprHash> ReprHash for $ty<T> {
+            #[inline(always)]
+            fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+                *offset_of = 0;
+                T::repr_hash(hasher, offset_of);
+                *offset_of = 0;
+                T::repr_hash(hasher, offset_of);
+            }
+        }
+
+        impl<T: SerializeInner> SerializeInner for $ty<T> {
+            const IS_ZERO_COPY: bool = false;
+            const ZERO_COPY_MISMATCH: bool = false;
+
+            #[inline(always)]
+            fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+                let $this = self;
+                backend.write("start", $start)?;
+                backend.write("end", $end)
+            }
+        }
+
+        impl<T: DeserializeInner> DeserializeInner for $ty<T> {
+            #[inline(always)]
+            fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+                let start = T::_deserialize_full_inner(backend)?;
+                let end = T::_deserialize_full_inner(backend)?;
+                Ok($new(start, end))
+            }
+            type DeserType<'a> = $ty<<T as DeserializeInner>::DeserType<'a>>;
+            #[inline(always)]
+            fn _deserialize_eps_inner<'a>(
+                backend: &mut SliceWithPos<'a>,
+            ) -> deser::Result<Self::DeserType<'a>> {
+                let start = T::_deserialize_eps_inner(backend)?;
+                let end = T::_deserialize_eps_inner(backend)?;
+                Ok($new(start, end))
+            }
+        }
+
+        impl<T: DeserializeMutInner> DeserializeMutInner for $ty<T> {
+            type DeserMutType<'a> = $ty<<T as DeserializeMutInner>::DeserMutType<'a>>;
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner<'a>(
+                backend: &mut SliceWithPosMut<'a>,
+            ) -> deser::Result<Self::DeserMutType<'a>> {
+                let start = T::_deserialize_eps_mut_inner(backend)?;
+                let end = T::_deserialize_eps_mut_inner(backend)?;
+                Ok($new(start, end))
+            }
+        }
+    };
+}
+
+impl_range!(Range, |start, end| Range { start, end }, |range| (
+    &range.start,
+    &range.end
+));
+impl_range!(RangeInclusive, RangeInclusive::new, |range| (
+    range.start(),
+    range.end()
+));
+
+// Results are deep-copy types serialized, as options, as a one-byte tag
+// (0 for Ok, 1 for Err) followed by the value.
+
+impl<T, E> CopyType for core::result::Result<T, E> {
+    type Copy = Deep;
+}
+
+impl<T: TypeHash, E: TypeHash> TypeHash for core::result::Result<T, E> {
+    #[inline(always)]
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        "Result".hash(hasher);
+        T::type_hash(hasher);
+        E::type_hash(hasher);
+    }
+}
+
+impl<T: ReprHash, E: ReprHash> ReprHash for core::result::Result<T, E> {
+    #[inline(always)]
+    fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+        *offset_of = 0;
+        T::repr_hash(hasher, offset_of);
+        *offset_of = 0;
+        E::repr_hash(hasher, offset_of);
+    }
+}
+
+impl<T: SerializeInner, E: SerializeInner> SerializeInner for core::result::Result<T, E> {
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        match self {
+            Ok(val) => {
+                backend.write("Tag", &0_u8)?;
+                backend.write("Ok", val)
+            }
+            Err(err) => {
+                backend.write("Tag", &1_u8)?;
+                backend.write("Err", err)
+            }
+        }
+    }
+}
+
+impl<T: DeserializeInner, E: DeserializeInner> DeserializeInner for core::result::Result<T, E> {
+    #[inline(always)]
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        let tag = u8::_deserialize_full_inner(backend)?;
+        match tag {
+            0 => Ok(Ok(T::_deserialize_full_inner(backend)?)),
+            1 => Ok(Err(E::_deserialize_full_inner(backend)?)),
+            _ => Err(deser::Error::InvalidTag(tag as usize)),
+        }
+    }
+    type DeserType<'a> = core::result::Result<
+        <T as DeserializeInner>::DeserType<'a>,
+        <E as DeserializeInner>::DeserType<'a>,
+    >;
+    #[inline(always)]
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        let tag = u8::_deserialize_full_inner(backend)?;
+        match tag {
+            0 => Ok(Ok(T::_deserialize_eps_inner(backend)?)),
+            1 => Ok(Err(E::_deserialize_eps_inner(backend)?)),
+            _ => Err(deser::Error::InvalidTag(tag as usize)),
+        }
+    }
+}
+
+impl<T: DeserializeMutInner, E: DeserializeMutInner> DeserializeMutInner
+    for core::result::Result<T, E>
+{
+    type DeserMutType<'a> = core::result::Result<
+        <T as DeserializeMutInner>::DeserMutType<'a>,
+        <E as DeserializeMutInner>::DeserMutType<'a>,
+    >;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        let tag = u8::_deserialize_full_inner(backend)?;
+        match tag {
+            0 => Ok(Ok(T::_deserialize_eps_mut_inner(backend)?)),
+            1 => Ok(Err(E::_deserialize_eps_mut_inner(backend)?)),
+            _ => Err(deser::Error::InvalidTag(tag as usize)),
+        }
+    }
+}
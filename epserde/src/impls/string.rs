@@ -16,8 +16,9 @@ use core::hash::Hash;
 use deser::*;
 use ser::*;
 
+// Vectors and boxed slices of strings are serialized as string pools.
 impl CopyType for String {
-    type Copy = Deep;
+    type Copy = Pool;
 }
 
 #[cfg(all(feature = "alloc", not(feature = "std")))]
@@ -83,7 +84,7 @@ impl DeserializeInner for String {
 }
 
 impl CopyType for Box<str> {
-    type Copy = Deep;
+    type Copy = Pool;
 }
 
 impl SerializeInner for Box<str> {
@@ -109,3 +110,24 @@ impl DeserializeInner for Box<str> {
         String::_deserialize_eps_inner(backend)
     }
 }
+
+impl DeserializeMutInner for String {
+    type DeserMutType<'a> = &'a mut str;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        let slice = deserialize_eps_mut_slice_zero(backend)?;
+        Ok(unsafe { core::str::from_utf8_unchecked_mut(slice) })
+    }
+}
+
+impl DeserializeMutInner for Box<str> {
+    type DeserMutType<'a> = &'a mut str;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        String::_deserialize_eps_mut_inner(backend)
+    }
+}
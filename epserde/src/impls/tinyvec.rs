@@ -0,0 +1,61 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Implementations for [`TinyVec`] and [`ArrayVec`] (available with the
+`tinyvec` feature).
+
+Both types are serialized with the layout of a `Vec<T>`, and they have the
+same ε-copy deserialization type of `Vec<T>` (e.g., a slice if `T` is
+zero-copy). Full deserialization of an [`ArrayVec`] fails with
+[`deser::Error::InvalidValue`] if the elements do not fit its capacity.
+
+*/
+
+use crate::impls::vec::impl_vec_layout;
+use crate::prelude::*;
+use ::tinyvec::{Array, ArrayVec, TinyVec};
+use core::hash::Hash;
+
+macro_rules! impl_type_info {
+    ($ty:ident) => {
+        impl<A: Array> TypeHash for $ty<A>
+        where
+            A::Item: TypeHash,
+        {
+            fn type_hash(hasher: &mut impl core::hash::Hasher) {
+                concat!("tinyvec::", stringify!($ty)).hash(hasher);
+                A::Item::type_hash(hasher);
+            }
+        }
+
+        impl<A: Array> ReprHash for $ty<A>
+        where
+            A::Item: ReprHash,
+        {
+            fn repr_hash(hasher: &mut impl core::hash::Hasher, offset_of: &mut usize) {
+                *offset_of = 0;
+                A::Item::repr_hash(hasher, offset_of);
+            }
+        }
+    };
+}
+
+impl_type_info!(TinyVec);
+impl_type_info!(ArrayVec);
+
+impl_vec_layout!([A: Array] TinyVec<A>, A::Item, |v: Vec<A::Item>| Ok(v
+    .into_iter()
+    .collect()));
+
+impl_vec_layout!([A: Array] ArrayVec<A>, A::Item, |v: Vec<A::Item>| {
+    if v.len() > A::CAPACITY {
+        return Err(deser::Error::InvalidValue("tinyvec::ArrayVec"));
+    }
+    Ok(v.into_iter().collect())
+});
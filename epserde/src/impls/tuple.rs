@@ -9,9 +9,13 @@
 
 Implementations for tuples.
 
-For the time being, we only support tuples of up to 10 elements all of which
-are [`ZeroCopy`]. For tuples of more than 10 elements, or tuples with elements
-that are not [`ZeroCopy`], you must use [`epserde_derive::Epserde`] on a newtype.
+We support tuples of up to 11 elements. Tuples whose elements are all [`ZeroCopy`]
+are zero-copy, and they are ε-copy deserialized as a reference. Otherwise, tuples
+are deep-copy, and their deserialization type is the tuple of the deserialization
+types of their elements: for example, `(Vec<u32>, String)` is
+ε-copy deserialized as `(&[u32], &str)`.
+For tuples of more than 11 elements, you must use
+[`epserde_derive::Epserde`] on a newtype.
 
 */
 use crate::deser::DeserializeInner;
@@ -21,10 +25,26 @@ use core::hash::Hash;
 use deser::*;
 use ser::*;
 
+/// Compute the [`CopySelector`] of a tuple as the conjunction
+/// of the selectors of its elements.
+macro_rules! tuple_copy {
+    ($t:ident) => {
+        // Normalize the selector to Zero or Deep
+        <<$t as CopyType>::Copy as CopySelector>::And<Zero>
+    };
+    ($t:ident, $($rest:ident),+) => {
+        <<$t as CopyType>::Copy as CopySelector>::And<tuple_copy!($($rest),+)>
+    };
+}
+
 macro_rules! impl_tuples {
-    ($($t:ident),*) => {
-        impl<$($t: ZeroCopy,)*> CopyType for ($($t,)*)  {
-            type Copy = Zero;
+    ($($t:ident $i:tt),*) => {
+        impl<$($t: CopyType,)*> CopyType for ($($t,)*)  {
+            type Copy = tuple_copy!($($t),*);
+		}
+
+        impl<$($t: CopyType<Copy = Zero>,)*> ZeroOptionCopyType for ($($t,)*)  {
+            type OptionCopy = Deep;
 		}
 
 		impl<$($t: TypeHash,)*> TypeHash for ($($t,)*)
@@ -40,18 +60,27 @@ macro_rules! impl_tuples {
             }
         }
 
-		impl<$($t: ReprHash,)*> ReprHash for ($($t,)*)
+		impl<$($t: CopyType + ReprHash,)*> ReprHash for ($($t,)*)
         {
             #[inline(always)]
             fn repr_hash(
                 hasher: &mut impl core::hash::Hasher,
                 offset_of: &mut usize,
             ) {
-                $(
-                    let curr_offset_of = *offset_of;
-                    <$t>::repr_hash(hasher, offset_of);
-                    *offset_of = curr_offset_of + core::mem::size_of::<$t>();
-                )*
+                if <<Self as CopyType>::Copy as CopySelector>::IS_ZERO_COPY {
+                    $(
+                        let curr_offset_of = *offset_of;
+                        <$t>::repr_hash(hasher, offset_of);
+                        *offset_of = curr_offset_of + core::mem::size_of::<$t>();
+                    )*
+                } else {
+                    // Elements are serialized in isolation, as fields of
+                    // deep-copy structures.
+                    $(
+                        *offset_of = 0;
+                        <$t>::repr_hash(hasher, offset_of);
+                    )*
+                }
             }
         }
 
@@ -67,40 +96,144 @@ macro_rules! impl_tuples {
             }
         }
 
-		impl<$($t: ZeroCopy + TypeHash + ReprHash,)*> SerializeInner for ($($t,)*) {
-            const IS_ZERO_COPY: bool = true;
+		impl<$($t: CopyType + SerializeInner,)*> SerializeInner for ($($t,)*)
+        where
+            ($($t,)*): SerializeHelper<<($($t,)*) as CopyType>::Copy>,
+        {
+            const IS_ZERO_COPY: bool = true $(&& <$t>::IS_ZERO_COPY)*;
             const ZERO_COPY_MISMATCH: bool = false;
 
+            #[inline(always)]
+            fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+                SerializeHelper::_serialize_inner(self, backend)
+            }
+        }
+
+		impl<$($t: ZeroCopy + SerializeInner,)*> SerializeHelper<Zero> for ($($t,)*) {
             #[inline(always)]
             fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
                 serialize_zero(backend, self)
             }
         }
 
-		impl<$($t: ZeroCopy + TypeHash + ReprHash + 'static,)*> DeserializeInner for ($($t,)*) {
-            type DeserType<'a> = &'a ($($t,)*);
+		impl<$($t: CopyType + SerializeInner,)*> SerializeHelper<Deep> for ($($t,)*) {
+            #[inline(always)]
+            fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+                $(
+                    backend.write(stringify!($i), &self.$i)?;
+                )*
+                Ok(())
+            }
+        }
+
+		impl<$($t: CopyType + DeserializeInner + 'static,)*> DeserializeInner for ($($t,)*)
+        where
+            ($($t,)*): DeserializeHelper<<($($t,)*) as CopyType>::Copy, FullType = ($($t,)*)>,
+        {
+            const MUST_CHECK_ZERO_COPY: bool = false $(|| <$t>::MUST_CHECK_ZERO_COPY)*;
+            type DeserType<'a> = <($($t,)*) as DeserializeHelper<<($($t,)*) as CopyType>::Copy>>::DeserType<'a>;
+
+            #[inline(always)]
             fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
-                deserialize_full_zero::<($($t,)*)>(backend)
+                <($($t,)*) as DeserializeHelper<<($($t,)*) as CopyType>::Copy>>::_deserialize_full_inner_impl(backend)
             }
 
+            #[inline(always)]
             fn _deserialize_eps_inner<'a>(
+                backend: &mut SliceWithPos<'a>,
+                ) -> deser::Result<Self::DeserType<'a>> {
+                <($($t,)*) as DeserializeHelper<<($($t,)*) as CopyType>::Copy>>::_deserialize_eps_inner_impl(backend)
+            }
+
+            fn _check_zero_copy(bytes: &[u8]) -> deser::Result<()> {
+                $(
+                    <$t>::_check_zero_copy(
+                        &bytes[core::mem::offset_of!(Self, $i)..][..core::mem::size_of::<$t>()],
+                    )?;
+                )*
+                Ok(())
+            }
+        }
+
+		impl<$($t: ZeroCopy + DeserializeInner + 'static,)*> DeserializeHelper<Zero> for ($($t,)*) {
+            type FullType = Self;
+            type DeserType<'a> = &'a ($($t,)*);
+
+            #[inline(always)]
+            fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+                deserialize_full_zero::<($($t,)*)>(backend)
+            }
+
+            #[inline(always)]
+            fn _deserialize_eps_inner_impl<'a>(
                 backend: &mut SliceWithPos<'a>,
                 ) -> deser::Result<Self::DeserType<'a>> {
                 deserialize_eps_zero::<($($t,)*)>(backend)
             }
+        }
+
+		impl<$($t: CopyType + DeserializeInner + 'static,)*> DeserializeHelper<Deep> for ($($t,)*) {
+            type FullType = Self;
+            type DeserType<'a> = ($(<$t as DeserializeInner>::DeserType<'a>,)*);
+
+            #[inline(always)]
+            fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+                Ok(($(<$t>::_deserialize_full_inner(backend)?,)*))
+            }
+
+            #[inline(always)]
+            fn _deserialize_eps_inner_impl<'a>(
+                backend: &mut SliceWithPos<'a>,
+                ) -> deser::Result<Self::DeserType<'a>> {
+                Ok(($(<$t>::_deserialize_eps_inner(backend)?,)*))
+            }
+        }
+
+		impl<$($t: CopyType + DeserializeMutInner + 'static,)*> DeserializeMutInner for ($($t,)*)
+        where
+            ($($t,)*): DeserializeHelper<<($($t,)*) as CopyType>::Copy, FullType = ($($t,)*)>,
+            ($($t,)*): DeserializeMutHelper<<($($t,)*) as CopyType>::Copy>,
+        {
+            type DeserMutType<'a> = <($($t,)*) as DeserializeMutHelper<<($($t,)*) as CopyType>::Copy>>::DeserMutType<'a>;
+
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner<'a>(
+                backend: &mut SliceWithPosMut<'a>,
+                ) -> deser::Result<Self::DeserMutType<'a>> {
+                <($($t,)*) as DeserializeMutHelper<<($($t,)*) as CopyType>::Copy>>::_deserialize_eps_mut_inner_impl(backend)
+            }
+        }
+
+		impl<$($t: ZeroCopy + DeserializeInner + 'static,)*> DeserializeMutHelper<Zero> for ($($t,)*) {
+            type DeserMutType<'a> = &'a mut ($($t,)*);
+
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner_impl<'a>(
+                backend: &mut SliceWithPosMut<'a>,
+                ) -> deser::Result<Self::DeserMutType<'a>> {
+                deserialize_eps_mut_zero::<($($t,)*)>(backend)
+            }
+        }
+
+		impl<$($t: CopyType + DeserializeMutInner + 'static,)*> DeserializeMutHelper<Deep> for ($($t,)*) {
+            type DeserMutType<'a> = ($(<$t as DeserializeMutInner>::DeserMutType<'a>,)*);
+
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner_impl<'a>(
+                backend: &mut SliceWithPosMut<'a>,
+                ) -> deser::Result<Self::DeserMutType<'a>> {
+                Ok(($(<$t>::_deserialize_eps_mut_inner(backend)?,)*))
+            }
         }
     };
 }
 
 macro_rules! impl_tuples_muncher {
-    ($ty:ident, $($t:ident),*) => {
-        impl_tuples!($ty, $($t),*);
-        impl_tuples_muncher!($($t),*);
-    };
-    ($ty:ident) => {
-        impl_tuples!($ty);
+    ([$($done:tt)*] $t:ident $i:tt $(, $rest_t:ident $rest_i:tt)*) => {
+        impl_tuples!($($done)* $t $i);
+        impl_tuples_muncher!([$($done)* $t $i,] $($rest_t $rest_i),*);
     };
-    () => {};
+    ([$($done:tt)*]) => {};
 }
 
-impl_tuples_muncher!(T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_tuples_muncher!([] T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10);
@@ -65,6 +65,23 @@ impl<T: DeepCopy + SerializeInner> SerializeHelper<Deep> for Vec<T> {
     }
 }
 
+impl<T: CopyType<Copy = Pool> + SerializeInner + AsRef<str>> SerializeHelper<Pool> for Vec<T> {
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_iter_pool(backend, self.iter())
+    }
+}
+
+impl<T: AtomicCopy> SerializeHelper<Atomic> for Vec<T>
+where
+    T::NonAtomic: SerializeInner,
+{
+    #[inline(always)]
+    fn _serialize_inner(&self, backend: &mut impl WriteWithNames) -> ser::Result<()> {
+        serialize_slice_atomic(backend, self)
+    }
+}
+
 // This delegates to a private helper trait which we can specialize on in stable rust
 impl<T: CopyType + DeserializeInner + 'static> DeserializeInner for Vec<T>
 where
@@ -113,3 +130,213 @@ impl<T: DeepCopy + DeserializeInner + 'static> DeserializeHelper<Deep> for Vec<T
         deserialize_eps_vec_deep::<T>(backend)
     }
 }
+
+impl<T: CopyType<Copy = Pool> + DeserializeInner + for<'b> From<&'b str> + 'static>
+    DeserializeHelper<Pool> for Vec<T>
+{
+    type FullType = Self;
+    type DeserType<'a> = StrPool<'a>;
+    #[inline(always)]
+    fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        deserialize_full_vec_pool::<T>(backend)
+    }
+    #[inline(always)]
+    fn _deserialize_eps_inner_impl<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<<Self as DeserializeInner>::DeserType<'a>> {
+        deserialize_eps_pool(backend)
+    }
+}
+
+impl<T: AtomicCopy + 'static> DeserializeHelper<Atomic> for Vec<T>
+where
+    T::NonAtomic: DeserializeInner,
+{
+    type FullType = Self;
+    type DeserType<'a> = &'a [T::NonAtomic];
+    #[inline(always)]
+    fn _deserialize_full_inner_impl(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        deserialize_full_vec_atomic(backend)
+    }
+    #[inline(always)]
+    fn _deserialize_eps_inner_impl<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        deserialize_eps_slice_zero(backend)
+    }
+}
+
+impl<T: CopyType + DeserializeMutInner + 'static> DeserializeMutInner for Vec<T>
+where
+    Vec<T>: DeserializeHelper<<T as CopyType>::Copy, FullType = Vec<T>>,
+    Vec<T>: DeserializeMutHelper<<T as CopyType>::Copy>,
+{
+    type DeserMutType<'a> =
+        <Vec<T> as DeserializeMutHelper<<T as CopyType>::Copy>>::DeserMutType<'a>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<<Vec<T> as DeserializeMutHelper<<T as CopyType>::Copy>>::DeserMutType<'a>>
+    {
+        <Vec<T> as DeserializeMutHelper<<T as CopyType>::Copy>>::_deserialize_eps_mut_inner_impl(
+            backend,
+        )
+    }
+}
+
+impl<T: ZeroCopy + DeserializeInner + 'static> DeserializeMutHelper<Zero> for Vec<T> {
+    type DeserMutType<'a> = &'a mut [T];
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        deserialize_eps_mut_slice_zero(backend)
+    }
+}
+
+impl<T: DeepCopy + DeserializeMutInner + 'static> DeserializeMutHelper<Deep> for Vec<T> {
+    type DeserMutType<'a> = Vec<<T as DeserializeMutInner>::DeserMutType<'a>>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        deserialize_eps_mut_vec_deep::<T>(backend)
+    }
+}
+
+impl<T: CopyType<Copy = Pool> + DeserializeMutInner + for<'b> From<&'b str> + 'static>
+    DeserializeMutHelper<Pool> for Vec<T>
+{
+    type DeserMutType<'a> = StrPoolMut<'a>;
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        deserialize_eps_mut_pool(backend)
+    }
+}
+
+impl<T: AtomicCopy + 'static> DeserializeMutHelper<Atomic> for Vec<T>
+where
+    T::NonAtomic: DeserializeInner,
+{
+    type DeserMutType<'a> = &'a mut [T];
+    #[inline(always)]
+    fn _deserialize_eps_mut_inner_impl<'a>(
+        backend: &mut SliceWithPosMut<'a>,
+    ) -> deser::Result<Self::DeserMutType<'a>> {
+        deserialize_eps_mut_slice_atomic(backend)
+    }
+}
+
+/// Implement serialization and deserialization for a vector-like type
+/// with the layout of a `Vec<T>`.
+///
+/// The arguments are the generic parameters of the type (in square
+/// brackets), the type, the type of its elements, and a function converting
+/// a `Vec` of elements into the type, returning a [`deser::Result`].
+/// The type must dereference to a slice of its elements.
+///
+/// The ε-copy deserialization type is the same of `Vec<T>` (e.g., a slice if
+/// `T` is zero-copy).
+#[allow(unused_macros)]
+macro_rules! impl_vec_layout {
+    ([$($generics:tt)*] $ty:ty, $item:ty, $from_vec:expr) => {
+        impl<$($generics)*> $crate::traits::CopyType for $ty {
+            type Copy = $crate::traits::Deep;
+        }
+
+        impl<$($generics)*> $crate::ser::SerializeInner for $ty
+        where
+            $item: $crate::traits::CopyType + $crate::ser::SerializeInner + $crate::traits::TypeHash,
+            $ty: $crate::ser::SerializeHelper<<$item as $crate::traits::CopyType>::Copy>,
+        {
+            const IS_ZERO_COPY: bool = false;
+            const ZERO_COPY_MISMATCH: bool = false;
+            fn _serialize_inner(
+                &self,
+                backend: &mut impl $crate::ser::WriteWithNames,
+            ) -> $crate::ser::Result<()> {
+                $crate::ser::SerializeHelper::_serialize_inner(self, backend)
+            }
+        }
+
+        impl<$($generics)*> $crate::ser::SerializeHelper<$crate::traits::Zero> for $ty
+        where
+            $item: $crate::traits::ZeroCopy + $crate::ser::SerializeInner,
+        {
+            #[inline(always)]
+            fn _serialize_inner(
+                &self,
+                backend: &mut impl $crate::ser::WriteWithNames,
+            ) -> $crate::ser::Result<()> {
+                $crate::ser::helpers::serialize_slice_zero(backend, self)
+            }
+        }
+
+        impl<$($generics)*> $crate::ser::SerializeHelper<$crate::traits::Deep> for $ty
+        where
+            $item: $crate::traits::DeepCopy + $crate::ser::SerializeInner,
+        {
+            #[inline(always)]
+            fn _serialize_inner(
+                &self,
+                backend: &mut impl $crate::ser::WriteWithNames,
+            ) -> $crate::ser::Result<()> {
+                $crate::ser::helpers::serialize_slice_deep(backend, self)
+            }
+        }
+
+        impl<$($generics)*> $crate::ser::SerializeHelper<$crate::traits::Pool> for $ty
+        where
+            $item: $crate::traits::CopyType<Copy = $crate::traits::Pool>
+                + $crate::ser::SerializeInner
+                + AsRef<str>,
+        {
+            #[inline(always)]
+            fn _serialize_inner(
+                &self,
+                backend: &mut impl $crate::ser::WriteWithNames,
+            ) -> $crate::ser::Result<()> {
+                $crate::ser::helpers::serialize_iter_pool(backend, self.iter())
+            }
+        }
+
+        impl<$($generics)*> $crate::deser::DeserializeInner for $ty
+        where
+            Vec<$item>: $crate::deser::DeserializeInner,
+        {
+            type DeserType<'a> = <Vec<$item> as $crate::deser::DeserializeInner>::DeserType<'a>;
+            #[inline(always)]
+            fn _deserialize_full_inner(
+                backend: &mut impl $crate::deser::ReadWithPos,
+            ) -> $crate::deser::Result<Self> {
+                ($from_vec)(<Vec<$item> as $crate::deser::DeserializeInner>::_deserialize_full_inner(backend)?)
+            }
+
+            #[inline(always)]
+            fn _deserialize_eps_inner<'a>(
+                backend: &mut $crate::deser::SliceWithPos<'a>,
+            ) -> $crate::deser::Result<Self::DeserType<'a>> {
+                <Vec<$item> as $crate::deser::DeserializeInner>::_deserialize_eps_inner(backend)
+            }
+        }
+
+        impl<$($generics)*> $crate::deser::DeserializeMutInner for $ty
+        where
+            Vec<$item>: $crate::deser::DeserializeMutInner,
+        {
+            type DeserMutType<'a> =
+                <Vec<$item> as $crate::deser::DeserializeMutInner>::DeserMutType<'a>;
+            #[inline(always)]
+            fn _deserialize_eps_mut_inner<'a>(
+                backend: &mut $crate::deser::SliceWithPosMut<'a>,
+            ) -> $crate::deser::Result<Self::DeserMutType<'a>> {
+                <Vec<$item> as $crate::deser::DeserializeMutInner>::_deserialize_eps_mut_inner(backend)
+            }
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use impl_vec_layout;
@@ -33,10 +33,15 @@ pub mod prelude {
     pub use crate::deser::Deserialize;
     pub use crate::deser::DeserializeHelper;
     pub use crate::deser::DeserializeInner;
+    pub use crate::deser::DeserializeMut;
+    pub use crate::deser::DeserializeMutHelper;
+    pub use crate::deser::DeserializeMutInner;
     pub use crate::deser::Flags;
     pub use crate::deser::MemCase;
+    pub use crate::deser::MemCaseMut;
     pub use crate::deser::ReadWithPos;
     pub use crate::deser::SliceWithPos;
+    pub use crate::deser::SliceWithPosMut;
     pub use crate::ser;
     pub use crate::ser::Serialize;
     pub use crate::ser::SerializeHelper;
@@ -47,7 +52,10 @@ pub mod prelude {
 }
 
 /// (Major, Minor) version of the file format, this follows semantic versioning
-pub const VERSION: (u16, u16) = (1, 0);
+///
+/// Version 2 serializes vectors and boxed slices of strings as
+/// [string pools](deser::StrPool).
+pub const VERSION: (u16, u16) = (2, 0);
 
 /// Magic cookie, also used as endianess marker.
 pub const MAGIC: u64 = u64::from_ne_bytes(*b"epserde ");
@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Diagnostics for zero-copy mismatches.
+
+When a deep-copy type could be zero-copy, but it has been declared neither
+`#[zero_copy]` nor `#[deep_copy]` (see
+[`SerializeInner::ZERO_COPY_MISMATCH`]), serialization reports the mismatch
+according to a global [`MismatchPolicy`], which can be set with
+[`set_mismatch_policy`], unless a policy is specified by the
+[options](SerializeOptions::mismatch_policy) of the serialization.
+
+With the default policy, [`MismatchPolicy::Warn`], the mismatch is
+passed to a hook, which can be set with [`set_mismatch_hook`], once per type.
+The [default hook](default_mismatch_hook) prints a warning on standard error,
+or logs it using the [`log`](https://docs.rs/log) crate if the `log` feature
+is enabled. With [`MismatchPolicy::Strict`], serialization fails with
+[`Error::ZeroCopyMismatch`].
+
+*/
+
+use super::*;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Mutex, RwLock};
+
+/// What to do when serializing a type with a
+/// [zero-copy mismatch](SerializeInner::ZERO_COPY_MISMATCH).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[repr(u8)]
+pub enum MismatchPolicy {
+    /// Pass the name of the type to the [hook](set_mismatch_hook), once per type.
+    #[default]
+    Warn,
+    /// Do nothing.
+    Ignore,
+    /// Fail with [`Error::ZeroCopyMismatch`].
+    Strict,
+}
+
+/// A hook receiving the name of the types with a
+/// [zero-copy mismatch](SerializeInner::ZERO_COPY_MISMATCH).
+pub type MismatchHook = fn(type_name: &'static str);
+
+static POLICY: AtomicU8 = AtomicU8::new(MismatchPolicy::Warn as u8);
+static HOOK: RwLock<MismatchHook> = RwLock::new(default_mismatch_hook);
+static REPORTED: Mutex<Option<HashSet<&'static str>>> = Mutex::new(None);
+
+/// Set the global policy for zero-copy mismatches.
+pub fn set_mismatch_policy(policy: MismatchPolicy) {
+    POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// Return the global policy for zero-copy mismatches.
+pub fn mismatch_policy() -> MismatchPolicy {
+    match POLICY.load(Ordering::Relaxed) {
+        0 => MismatchPolicy::Warn,
+        1 => MismatchPolicy::Ignore,
+        _ => MismatchPolicy::Strict,
+    }
+}
+
+/// Set the global hook for zero-copy mismatches.
+///
+/// The hook is called at most once per type, even if it is replaced.
+pub fn set_mismatch_hook(hook: MismatchHook) {
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = hook;
+}
+
+/// The default hook for zero-copy mismatches.
+///
+/// It prints a warning on standard error, or, if the `log` feature is enabled,
+/// it logs it at the warning level.
+pub fn default_mismatch_hook(type_name: &'static str) {
+    #[cfg(feature = "log")]
+    log::warn!("Type {} is zero-copy, but it has not been declared as such; use the #[deep_copy] attribute to silence this warning", type_name);
+    #[cfg(not(feature = "log"))]
+    eprintln!("Type {} is zero-copy, but it has not been declared as such; use the #[deep_copy] attribute to silence this warning", type_name);
+}
+
+/// Report a zero-copy mismatch for the given type according to the given policy.
+pub fn report_mismatch(policy: MismatchPolicy, type_name: &'static str) -> Result<()> {
+    match policy {
+        MismatchPolicy::Ignore => Ok(()),
+        MismatchPolicy::Strict => Err(Error::ZeroCopyMismatch(type_name)),
+        MismatchPolicy::Warn => {
+            let first = REPORTED
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get_or_insert_with(HashSet::new)
+                .insert(type_name);
+            if first {
+                let hook = *HOOK.read().unwrap_or_else(|e| e.into_inner());
+                hook(type_name);
+            }
+            Ok(())
+        }
+    }
+}
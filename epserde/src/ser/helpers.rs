@@ -11,17 +11,60 @@ Helpers for serialization.
 
 */
 
-use super::{SerializeInner, WriteWithNames};
+use super::{SerializeInner, WriteWithNames, WriteWithPos};
 use crate::ser;
 use crate::traits::*;
 
+use core::marker::PhantomData;
+
+struct AssertZeroCopy<V>(PhantomData<V>);
+
+impl<V: SerializeInner> AssertZeroCopy<V> {
+    const OK: () = assert!(
+        V::IS_ZERO_COPY,
+        "Cannot serialize a type declared as zero-copy as it is not zero-copy"
+    );
+}
+
+/// Check [that the type is actually zero-copy](SerializeInner::IS_ZERO_COPY).
+///
+/// The check is performed at compile time: if `V` is not zero-copy,
+/// the evaluation of a constant will fail when the function is instantiated,
+/// and the compiler will report the offending type.
+#[inline(always)]
 pub fn check_zero_copy<V: SerializeInner>() {
-    if !V::IS_ZERO_COPY {
-        panic!(
-            "Cannot serialize type {} declared as zero-copy as it is not zero-copy",
-            core::any::type_name::<V>()
-        );
-    }
+    #[allow(clippy::let_unit_value)]
+    let () = AssertZeroCopy::<V>::OK;
+}
+
+/// Compile-time lint for [zero-copy mismatches](SerializeInner::ZERO_COPY_MISMATCH).
+///
+/// The parameter is the [`CopySelector`] obtained by combining the selectors of
+/// all fields of a type with [`CopySelector::And`]. The derive macros call
+/// [`check`](ZeroCopyMismatch::check) for types without parameters that are
+/// declared neither `#[zero_copy]` nor `#[deep_copy]`: if all fields are
+/// zero-copy, the call resolves to a deprecated function, and the compiler
+/// issues a warning pointing at the type.
+#[doc(hidden)]
+pub struct ZeroCopyMismatch<S: CopySelector>(PhantomData<S>);
+
+impl ZeroCopyMismatch<Zero> {
+    #[deprecated(
+        note = "this type is zero-copy, but it has not been declared as such; use the #[zero_copy] attribute, or the #[deep_copy] attribute to silence this warning"
+    )]
+    pub const fn check() {}
+}
+
+impl ZeroCopyMismatch<Deep> {
+    pub const fn check() {}
+}
+
+impl ZeroCopyMismatch<Pool> {
+    pub const fn check() {}
+}
+
+impl ZeroCopyMismatch<Atomic> {
+    pub const fn check() {}
 }
 
 /// Serialize a zero-copy structure by writing its bytes properly [aligned](WriteWithNames::align).
@@ -65,9 +108,19 @@ pub fn serialize_slice_zero<V: SerializeInner + ZeroCopy>(
     backend.write_bytes::<V>(buffer)
 }
 
-pub fn check_mismatch<V: SerializeInner>() {
+/// Check [whether the type might actually be zero-copy](SerializeInner::ZERO_COPY_MISMATCH),
+/// and in that case report the mismatch as prescribed by the
+/// [policy of the backend](super::SerializeOptions::mismatch_policy), or by
+/// the [global policy](super::set_mismatch_policy) if the former is not set.
+pub fn check_mismatch<V: SerializeInner>(backend: &impl WriteWithPos) -> ser::Result<()> {
     if V::ZERO_COPY_MISMATCH {
-        eprintln!("Type {} is zero-copy, but it has not declared as such; use the #[deep_copy] attribute to silence this warning", core::any::type_name::<V>());
+        let policy = backend
+            .options()
+            .mismatch_policy
+            .unwrap_or_else(super::mismatch_policy);
+        super::report_mismatch(policy, core::any::type_name::<V>())
+    } else {
+        Ok(())
     }
 }
 
@@ -79,7 +132,7 @@ pub fn serialize_slice_deep<V: SerializeInner>(
     backend: &mut impl WriteWithNames,
     data: &[V],
 ) -> ser::Result<()> {
-    check_mismatch::<V>();
+    check_mismatch::<V>(backend)?;
     let len = data.len();
     backend.write("len", &len)?;
     for item in data.iter() {
@@ -87,3 +140,234 @@ pub fn serialize_slice_deep<V: SerializeInner>(
     }
     Ok(())
 }
+
+/// Serialize a sequence of zero-copy structures, given as a sequence of
+/// chunks of overall length `len`, with the same layout of [`serialize_slice_zero`].
+///
+/// Note that this method uses a `write_all` call for each chunk.
+///
+/// Here we check [that the type is actually zero-copy](SerializeInner::IS_ZERO_COPY).
+pub fn serialize_chunks_zero<'a, V: SerializeInner + ZeroCopy + 'a>(
+    backend: &mut impl WriteWithNames,
+    len: usize,
+    chunks: impl IntoIterator<Item = &'a [V]>,
+) -> ser::Result<()> {
+    check_zero_copy::<V>();
+
+    backend.write("len", &len)?;
+    backend.align::<V>()?;
+    for chunk in chunks {
+        let buffer = unsafe {
+            core::slice::from_raw_parts(chunk.as_ptr() as *const u8, core::mem::size_of_val(chunk))
+        };
+        backend.write_bytes::<V>(buffer)?;
+    }
+    Ok(())
+}
+
+/// Serialize a sequence of deep-copy structures, given as an iterator,
+/// with the same layout of [`serialize_slice_deep`].
+///
+/// Here we warn [that the type might actually be zero-copy](SerializeInner::ZERO_COPY_MISMATCH).
+pub fn serialize_iter_deep<'a, V: SerializeInner + 'a>(
+    backend: &mut impl WriteWithNames,
+    iter: impl ExactSizeIterator<Item = &'a V>,
+) -> ser::Result<()> {
+    check_mismatch::<V>(backend)?;
+    backend.write("len", &iter.len())?;
+    for item in iter {
+        backend.write("item", item)?;
+    }
+    Ok(())
+}
+
+/// Return the length of an iterator, if it is known in advance, that is, if
+/// its [size hint](Iterator::size_hint) is exact.
+fn exact_len(iter: &impl Iterator) -> Option<usize> {
+    match iter.size_hint() {
+        (lower, Some(upper)) if lower == upper => Some(lower),
+        _ => None,
+    }
+}
+
+/// Write the length of a sequence, returning the position at which it has been
+/// written and its length, if known in advance.
+///
+/// If the length is not known in advance, a placeholder is written, provided
+/// the backend [can back-patch it](WriteWithPos::can_patch).
+fn write_stream_len(
+    backend: &mut impl WriteWithNames,
+    iter: &impl Iterator,
+) -> ser::Result<(usize, Option<usize>)> {
+    let len = exact_len(iter);
+    if len.is_none() && !backend.can_patch() {
+        return Err(ser::Error::UnknownLength);
+    }
+    let pos = backend.pos();
+    backend.write("len", &len.unwrap_or(0))?;
+    Ok((pos, len))
+}
+
+/// Check that the number of items of a sequence is equal to its length,
+/// if it was known in advance, or back-patch the length otherwise.
+fn patch_stream_len(
+    backend: &mut impl WriteWithNames,
+    pos: usize,
+    len: Option<usize>,
+    count: usize,
+) -> ser::Result<()> {
+    match len {
+        Some(len) if len != count => Err(ser::Error::IteratorLengthMismatch {
+            expected: len,
+            actual: count,
+        }),
+        Some(_) => Ok(()),
+        None => backend.patch(pos, &count.to_ne_bytes()),
+    }
+}
+
+/// Serialize a sequence of zero-copy structures, given as an iterator
+/// of owned items, with the same layout of [`serialize_slice_zero`].
+///
+/// Items are buffered and written in chunks, so the sequence is never
+/// materialized in memory. If the length of the iterator is not known in
+/// advance (i.e., its [size hint](Iterator::size_hint) is not exact), the
+/// length is back-patched after writing the items, which requires a backend
+/// that [supports it](WriteWithPos::can_patch).
+///
+/// Here we check [that the type is actually zero-copy](SerializeInner::IS_ZERO_COPY).
+pub fn serialize_stream_zero<V: SerializeInner + ZeroCopy>(
+    backend: &mut impl WriteWithNames,
+    iter: impl IntoIterator<Item = V>,
+) -> ser::Result<()> {
+    const CHUNK_SIZE: usize = 1024;
+
+    fn write_chunk<V: SerializeInner + ZeroCopy>(
+        backend: &mut impl WriteWithNames,
+        chunk: &[V],
+    ) -> ser::Result<()> {
+        let buffer = unsafe {
+            core::slice::from_raw_parts(chunk.as_ptr() as *const u8, core::mem::size_of_val(chunk))
+        };
+        backend.write_bytes::<V>(buffer)
+    }
+
+    check_zero_copy::<V>();
+
+    let iter = iter.into_iter();
+    let (pos, len) = write_stream_len(backend, &iter)?;
+    backend.align::<V>()?;
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE.min(len.unwrap_or(CHUNK_SIZE)));
+    let mut count = 0;
+    for item in iter {
+        if chunk.len() == CHUNK_SIZE {
+            write_chunk(backend, &chunk)?;
+            chunk.clear();
+        }
+        chunk.push(item);
+        count += 1;
+    }
+    write_chunk(backend, &chunk)?;
+    patch_stream_len(backend, pos, len, count)
+}
+
+/// Serialize a sequence of deep-copy structures, given as an iterator
+/// of owned items, with the same layout of [`serialize_slice_deep`].
+///
+/// If the length of the iterator is not known in advance (i.e., its
+/// [size hint](Iterator::size_hint) is not exact), the length is
+/// back-patched after writing the items, which requires a backend that
+/// [supports it](WriteWithPos::can_patch).
+///
+/// Here we warn [that the type might actually be zero-copy](SerializeInner::ZERO_COPY_MISMATCH).
+pub fn serialize_stream_deep<V: SerializeInner>(
+    backend: &mut impl WriteWithNames,
+    iter: impl IntoIterator<Item = V>,
+) -> ser::Result<()> {
+    check_mismatch::<V>(backend)?;
+    let iter = iter.into_iter();
+    let (pos, len) = write_stream_len(backend, &iter)?;
+    let mut count = 0;
+    for item in iter {
+        backend.write("item", &item)?;
+        count += 1;
+    }
+    patch_stream_len(backend, pos, len, count)
+}
+
+/// Serialize a slice of [atomic](AtomicCopy) structures with the layout
+/// of a slice of the non-atomic type, loading each value with
+/// [relaxed](core::sync::atomic::Ordering::Relaxed) ordering.
+pub fn serialize_slice_atomic<V: AtomicCopy>(
+    backend: &mut impl WriteWithNames,
+    data: &[V],
+) -> ser::Result<()>
+where
+    V::NonAtomic: SerializeInner,
+{
+    serialize_stream_zero(backend, data.iter().map(V::load_relaxed))
+}
+
+/// Serialize a sequence of strings, given as an iterator, as a [string pool](crate::deser::StrPool),
+/// that is, as the offsets of the strings in their concatenation (plus a final
+/// offset equal to the length of the concatenation), with the layout of a slice
+/// of `usize`, followed by the concatenation, with the layout of a slice of `u8`.
+///
+/// Offsets are written in chunks, and the concatenation string by string.
+pub fn serialize_iter_pool<V: AsRef<str>>(
+    backend: &mut impl WriteWithNames,
+    iter: impl ExactSizeIterator<Item = V> + Clone,
+) -> ser::Result<()> {
+    const CHUNK_SIZE: usize = 1024;
+
+    fn write_offsets(backend: &mut impl WriteWithNames, offsets: &[usize]) -> ser::Result<()> {
+        let buffer = unsafe {
+            core::slice::from_raw_parts(
+                offsets.as_ptr() as *const u8,
+                core::mem::size_of_val(offsets),
+            )
+        };
+        backend.write_bytes::<usize>(buffer)
+    }
+
+    backend.write("len", &(iter.len() + 1))?;
+    backend.align::<usize>()?;
+    let mut offsets = [0_usize; CHUNK_SIZE];
+    let mut filled = 1;
+    let mut offset = 0;
+    for item in iter.clone() {
+        if filled == CHUNK_SIZE {
+            write_offsets(backend, &offsets)?;
+            filled = 0;
+        }
+        offset += item.as_ref().len();
+        offsets[filled] = offset;
+        filled += 1;
+    }
+    write_offsets(backend, &offsets[..filled])?;
+
+    backend.write("len", &offset)?;
+    backend.align::<u8>()?;
+    for item in iter {
+        backend.write_bytes::<u8>(item.as_ref().as_bytes())?;
+    }
+    Ok(())
+}
+
+/// Run `f` on the backend after raising its [minimum alignment](super::SerializeOptions::min_align)
+/// to at least `min_align`, restoring the previous minimum alignment afterwards.
+///
+/// The derive macros use this function to implement the field attribute
+/// `#[epserde(align = N)]`; deserialization must use
+/// [`deser::helpers::with_min_align`](crate::deser::helpers::with_min_align).
+pub fn with_min_align<B: WriteWithNames, R>(
+    backend: &mut B,
+    min_align: usize,
+    f: impl FnOnce(&mut B) -> ser::Result<R>,
+) -> ser::Result<R> {
+    let prev = backend.options().min_align;
+    backend.set_min_align(prev.max(min_align))?;
+    let result = f(backend);
+    backend.set_min_align(prev)?;
+    result
+}
@@ -29,6 +29,10 @@ pub mod write_with_names;
 pub use write_with_names::*;
 pub mod helpers;
 pub use helpers::*;
+pub mod diagnostics;
+pub use diagnostics::*;
+pub mod options;
+pub use options::*;
 pub mod write;
 pub use write::*;
 
@@ -43,9 +47,74 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub trait Serialize: TypeHash + ReprHash {
     /// Serialize the type using the given backend.
     fn serialize(&self, backend: &mut impl WriteNoStd) -> Result<usize> {
-        let mut write_with_pos = WriterWithPos::new(backend);
+        self.serialize_with(&SerializeOptions::DEFAULT, backend)
+    }
+
+    /// Serialize the type using the given backend and [options](SerializeOptions),
+    /// returning the number of bytes written.
+    ///
+    /// The data must be deserialized with matching
+    /// [options](crate::deser::DeserializeOptions).
+    fn serialize_with(
+        &self,
+        options: &SerializeOptions,
+        backend: &mut impl WriteNoStd,
+    ) -> Result<usize> {
+        if options.checksum {
+            let mut checksum_writer = ChecksumWriter::new(backend);
+            let mut write_with_pos = WriterWithPos::with_options(&mut checksum_writer, *options);
+            self.serialize_on_field_write(&mut write_with_pos)?;
+            let pos = write_with_pos.pos();
+            let checksum = checksum_writer.checksum();
+            backend.write_all(&checksum.to_ne_bytes())?;
+            backend.flush()?;
+            Ok(pos - options.base_offset + core::mem::size_of::<u64>())
+        } else {
+            let mut write_with_pos = WriterWithPos::with_options(backend, *options);
+            self.serialize_on_field_write(&mut write_with_pos)?;
+            Ok(write_with_pos.pos() - options.base_offset)
+        }
+    }
+
+    /// Serialize the type using the given seekable backend and
+    /// [options](SerializeOptions), returning the number of bytes written.
+    ///
+    /// Differently from [`Serialize::serialize_with`], this method makes it
+    /// possible to serialize sequences whose length is not known in advance
+    /// (see [`SerIter`](crate::impls::iter::SerIter)), as their length will be
+    /// back-patched once the sequence is exhausted. Back-patching is not
+    /// possible if a [checksum](SerializeOptions::checksum) is requested.
+    fn serialize_with_seek(
+        &self,
+        options: &SerializeOptions,
+        backend: &mut (impl std::io::Write + std::io::Seek),
+    ) -> Result<usize> {
+        if options.checksum {
+            return self.serialize_with(options, backend);
+        }
+        let mut write_with_pos = WriterWithPos::with_seek(backend, *options);
         self.serialize_on_field_write(&mut write_with_pos)?;
-        Ok(write_with_pos.pos())
+        Ok(write_with_pos.pos() - options.base_offset)
+    }
+
+    /// Serialize the type using the given backend without writing the
+    /// [header](write_header), returning the number of bytes written.
+    ///
+    /// This method is useful to embed ε-serde data in another format:
+    /// `base_offset` is the position of the first written byte with respect to
+    /// the start of the enclosing container, and zero-copy data will be
+    /// aligned with respect to the latter. Since there is no header, the type
+    /// must be checked by other means. The data can be deserialized with
+    /// [`deserialize_full_raw`](crate::deser::Deserialize::deserialize_full_raw)
+    /// or [`deserialize_eps_raw`](crate::deser::Deserialize::deserialize_eps_raw)
+    /// using the same base offset.
+    fn serialize_raw(&self, backend: &mut impl WriteNoStd, base_offset: usize) -> Result<usize> {
+        self.serialize_with(
+            &SerializeOptions::new()
+                .header(false)
+                .base_offset(base_offset),
+            backend,
+        )
     }
 
     /// Serialize the type using the given backend and return a [schema](Schema)
@@ -84,17 +153,18 @@ pub trait SerializeInner {
     /// Inner constant used by the derive macros to keep
     /// track recursively of whether the type
     /// satisfies the conditions for being zero-copy. It is checked
-    /// at runtime against the trait implemented by the type, and
+    /// at compile time against the trait implemented by the type, and
     /// if a [`ZeroCopy`] type has this constant set to `false`
-    /// serialization will panic.
+    /// code serializing the type will not compile.
     const IS_ZERO_COPY: bool;
 
     /// Inner constant used by the derive macros to keep
     /// track of whether all fields of a type are zero-copy
     /// but neither the attribute `#[zero_copy]` nor the attribute `#[deep_copy]`
     /// was specified. It is checked at runtime, and if it is true
-    /// a warning will be issued, as the type could be zero-copy,
-    /// which would be more efficient.
+    /// the mismatch will be [reported](diagnostics), as the type could be zero-copy,
+    /// which would be more efficient. For types without parameters,
+    /// the derive macros issue the warning at compile time, too.
     const ZERO_COPY_MISMATCH: bool;
 
     /// Serialize this structure using the given backend.
@@ -105,11 +175,14 @@ pub trait SerializeInner {
 /// methods in [`Serialize`].
 ///
 /// This implementation [writes a header](`write_header`) containing some hashes
-/// and debug information and then delegates to [WriteWithNames::write].
+/// and debug information, unless [disabled](SerializeOptions::header),
+/// and then delegates to [WriteWithNames::write].
 impl<T: SerializeInner + TypeHash + ReprHash> Serialize for T {
     /// Serialize the type using the given [`WriteWithNames`].
     fn serialize_on_field_write(&self, backend: &mut impl WriteWithNames) -> Result<()> {
-        write_header::<Self>(backend)?;
+        if backend.options().header {
+            write_header::<Self>(backend)?;
+        }
         backend.write("ROOT", self)?;
         backend.flush()
     }
@@ -123,6 +196,8 @@ pub fn write_header<T: TypeHash + ReprHash>(backend: &mut impl WriteWithNames) -
     backend.write("VERSION_MAJOR", &VERSION.0)?;
     backend.write("VERSION_MINOR", &VERSION.1)?;
     backend.write("USIZE_SIZE", &(core::mem::size_of::<usize>() as u8))?;
+    let min_align = backend.options().min_align;
+    backend.write("MIN_ALIGN", &min_align)?;
 
     let mut type_hasher = xxhash_rust::xxh3::Xxh3::new();
     T::type_hash(&mut type_hasher);
@@ -150,6 +225,17 @@ pub enum Error {
     WriteError,
     /// [`Serialize::store`] could not open the provided file.
     FileOpenError(std::io::Error),
+    /// The type could be zero-copy, but it has not been declared as such,
+    /// and the [mismatch policy](MismatchPolicy) is strict.
+    ZeroCopyMismatch(&'static str),
+    /// An iterator returned a number of items different from its length.
+    IteratorLengthMismatch { expected: usize, actual: usize },
+    /// The length of a sequence is not known in advance,
+    /// and the backend [cannot back-patch it](WriteWithPos::patch).
+    UnknownLength,
+    /// The backend does not support setting the given
+    /// [minimum alignment](WriteWithPos::set_min_align).
+    UnsupportedMinAlign(usize),
 }
 
 impl std::error::Error for Error {}
@@ -165,6 +251,29 @@ impl core::fmt::Display for Error {
                     error
                 )
             }
+            Self::ZeroCopyMismatch(type_name) => {
+                write!(
+                    f,
+                    "Type {} is zero-copy, but it has not been declared as such",
+                    type_name
+                )
+            }
+            Self::IteratorLengthMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "An iterator of length {} returned {} items during ε-serde serialization",
+                    expected, actual
+                )
+            }
+            Self::UnknownLength => write!(
+                f,
+                "The length of a sequence is not known in advance, and the ε-serde backend cannot back-patch it"
+            ),
+            Self::UnsupportedMinAlign(min_align) => write!(
+                f,
+                "The ε-serde backend does not support setting the minimum alignment to {}",
+                min_align
+            ),
         }
     }
 }
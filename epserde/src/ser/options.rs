@@ -0,0 +1,111 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ * SPDX-FileCopyrightText: 2023 Sebastiano Vigna
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+/*!
+
+Options for serialization.
+
+[`SerializeOptions`] modifies the behavior of a single serialization
+performed with [`Serialize::serialize_with`]. The options are carried by
+the [`WriteWithPos`] backend, so implementations of [`SerializeInner`] can
+access them using [`WriteWithPos::options`].
+
+Data serialized with some options must be deserialized with matching
+[`DeserializeOptions`](crate::deser::DeserializeOptions).
+
+*/
+
+use super::*;
+
+/// Options for [`Serialize::serialize_with`].
+///
+/// Options can be set by chaining methods starting from [`SerializeOptions::new`]:
+/// ```
+/// # use epserde::ser::SerializeOptions;
+/// let options = SerializeOptions::new().min_align(64).checksum(true);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SerializeOptions {
+    /// Whether to write the [header](write_header). Defaults to `true`.
+    pub header: bool,
+    /// The minimum alignment of zero-copy data, which will be aligned
+    /// to the maximum between this value and [`MaxSizeOf::max_size_of`].
+    /// Defaults to 1.
+    pub min_align: usize,
+    /// Whether to append to the serialized data a checksum of all
+    /// the preceding bytes. Defaults to `false`.
+    pub checksum: bool,
+    /// The policy for [zero-copy mismatches](SerializeInner::ZERO_COPY_MISMATCH).
+    /// Defaults to `None`, that is, to the [global policy](set_mismatch_policy).
+    pub mismatch_policy: Option<MismatchPolicy>,
+    /// The position of the first written byte with respect to the start
+    /// of an enclosing container, used to compute alignment
+    /// padding. Defaults to 0.
+    pub base_offset: usize,
+}
+
+impl SerializeOptions {
+    /// The default options, which yield the same behavior of [`Serialize::serialize`].
+    pub const DEFAULT: Self = Self {
+        header: true,
+        min_align: 1,
+        checksum: false,
+        mismatch_policy: None,
+        base_offset: 0,
+    };
+
+    /// Return the default options.
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// Set whether to write the header.
+    pub const fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Set the minimum alignment of zero-copy data.
+    ///
+    /// # Panics
+    ///
+    /// If `min_align` is not a power of two.
+    pub const fn min_align(mut self, min_align: usize) -> Self {
+        assert!(
+            min_align.is_power_of_two(),
+            "The minimum alignment must be a power of two"
+        );
+        self.min_align = min_align;
+        self
+    }
+
+    /// Set whether to append a checksum.
+    pub const fn checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Set the policy for zero-copy mismatches, overriding the global one.
+    pub const fn mismatch_policy(mut self, mismatch_policy: MismatchPolicy) -> Self {
+        self.mismatch_policy = Some(mismatch_policy);
+        self
+    }
+
+    /// Set the position of the first written byte with respect to the start
+    /// of an enclosing container.
+    pub const fn base_offset(mut self, base_offset: usize) -> Self {
+        self.base_offset = base_offset;
+        self
+    }
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -12,6 +12,7 @@ No-std support for writing while keeping track of the current position.
  */
 
 use crate::prelude::*;
+use crate::ser::SerializeOptions;
 
 /// [`std::io::Write`]-like trait for serialization that does not
 /// depend on [`std`].
@@ -30,7 +31,7 @@ pub trait WriteNoStd {
 }
 
 #[cfg(feature = "std")]
-use std::io::Write;
+use std::io::{Seek, SeekFrom, Write};
 
 #[cfg(feature = "std")]
 impl<W: Write> WriteNoStd for W {
@@ -50,8 +51,53 @@ impl<W: Write> WriteNoStd for W {
 /// [`std::io::Seek`] would be a requirement much stronger than needed.
 pub trait WriteWithPos: WriteNoStd {
     fn pos(&self) -> usize;
+
+    /// Return the [options](SerializeOptions) of the current serialization.
+    ///
+    /// The default implementation returns [`SerializeOptions::DEFAULT`].
+    fn options(&self) -> &SerializeOptions {
+        &SerializeOptions::DEFAULT
+    }
+
+    /// Set the [minimum alignment](SerializeOptions::min_align) of the
+    /// current serialization, which makes it possible to modify it
+    /// temporarily (see, e.g., [`with_min_align`](ser::helpers::with_min_align)).
+    ///
+    /// The default implementation, suitable for backends whose options
+    /// cannot be modified, returns [`UnsupportedMinAlign`](ser::Error::UnsupportedMinAlign)
+    /// unless `min_align` is the current minimum alignment.
+    fn set_min_align(&mut self, min_align: usize) -> ser::Result<()> {
+        if min_align == self.options().min_align {
+            Ok(())
+        } else {
+            Err(ser::Error::UnsupportedMinAlign(min_align))
+        }
+    }
+
+    /// Return whether bytes already written can be overwritten using
+    /// [`WriteWithPos::patch`].
+    ///
+    /// The default implementation returns `false`.
+    fn can_patch(&self) -> bool {
+        false
+    }
+
+    /// Overwrite bytes already written starting at position `pos`, leaving
+    /// the current position unchanged.
+    ///
+    /// This method is used to back-patch the length of sequences whose
+    /// length is not known in advance (see
+    /// [`serialize_stream_zero`](ser::helpers::serialize_stream_zero)). The
+    /// default implementation returns [`ser::Error::UnknownLength`].
+    fn patch(&mut self, _pos: usize, _buf: &[u8]) -> ser::Result<()> {
+        Err(ser::Error::UnknownLength)
+    }
 }
 
+/// A function overwriting bytes already written on a backend, given the
+/// distance of the first byte to overwrite from the current position.
+type PatchFn<F> = fn(&mut F, usize, &[u8]) -> ser::Result<()>;
+
 /// A wrapper for a [`WriteNoStd`] that implements [`WriteWithPos`]
 /// by keeping track of the current position.
 pub struct WriterWithPos<'a, F: WriteNoStd> {
@@ -59,16 +105,62 @@ pub struct WriterWithPos<'a, F: WriteNoStd> {
     backend: &'a mut F,
     /// How many bytes we have written from the start.
     pos: usize,
+    /// The options of the current serialization.
+    options: SerializeOptions,
+    /// How to overwrite bytes already written, if possible.
+    patch: Option<PatchFn<F>>,
 }
 
 impl<'a, F: WriteNoStd> WriterWithPos<'a, F> {
     #[inline(always)]
     /// Create a new [`WriterWithPos`] on top of a generic [`WriteNoStd`] `F`.
     pub fn new(backend: &'a mut F) -> Self {
-        Self { backend, pos: 0 }
+        Self::with_options(backend, SerializeOptions::DEFAULT)
+    }
+
+    #[inline(always)]
+    /// Create a new [`WriterWithPos`] on top of a generic [`WriteNoStd`] `F`
+    /// with the given [options](SerializeOptions).
+    pub fn with_options(backend: &'a mut F, options: SerializeOptions) -> Self {
+        Self {
+            backend,
+            pos: options.base_offset,
+            options,
+            patch: None,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    /// Create a new [`WriterWithPos`] on top of a [`Write`] `F` that
+    /// also implements [`Seek`], with the given [options](SerializeOptions).
+    ///
+    /// Differently from the other constructors, the resulting writer
+    /// [can overwrite](WriteWithPos::patch) bytes already written.
+    pub fn with_seek(backend: &'a mut F, options: SerializeOptions) -> Self
+    where
+        F: Write + Seek,
+    {
+        Self {
+            patch: Some(patch_seek::<F>),
+            ..Self::with_options(backend, options)
+        }
     }
 }
 
+#[cfg(feature = "std")]
+fn patch_seek<F: Write + Seek>(backend: &mut F, back: usize, buf: &[u8]) -> ser::Result<()> {
+    let to_io = |_| ser::Error::WriteError;
+    backend
+        .seek(SeekFrom::Current(-(back as i64)))
+        .map_err(to_io)?;
+    Write::write_all(backend, buf).map_err(to_io)?;
+    backend
+        .seek(SeekFrom::Current((back - buf.len()) as i64))
+        .map_err(to_io)?;
+    Ok(())
+}
+
 impl<'a, F: WriteNoStd> WriteNoStd for WriterWithPos<'a, F> {
     #[inline(always)]
     fn write_all(&mut self, buf: &[u8]) -> ser::Result<()> {
@@ -88,4 +180,68 @@ impl<'a, F: WriteNoStd> WriteWithPos for WriterWithPos<'a, F> {
     fn pos(&self) -> usize {
         self.pos
     }
+
+    #[inline(always)]
+    fn options(&self) -> &SerializeOptions {
+        &self.options
+    }
+
+    #[inline(always)]
+    fn set_min_align(&mut self, min_align: usize) -> ser::Result<()> {
+        self.options.min_align = min_align;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn can_patch(&self) -> bool {
+        self.patch.is_some()
+    }
+
+    fn patch(&mut self, pos: usize, buf: &[u8]) -> ser::Result<()> {
+        let patch = self.patch.ok_or(ser::Error::UnknownLength)?;
+        assert!(
+            pos + buf.len() <= self.pos,
+            "Cannot patch bytes that have not been written yet"
+        );
+        patch(self.backend, self.pos - pos, buf)
+    }
+}
+
+/// A wrapper for a [`WriteNoStd`] that computes a checksum of the written bytes.
+///
+/// It is used by [`Serialize::serialize_with`] to implement
+/// [`SerializeOptions::checksum`].
+pub struct ChecksumWriter<'a, F: WriteNoStd> {
+    /// What we actually write on.
+    backend: &'a mut F,
+    /// The hasher of the bytes written so far.
+    hasher: xxhash_rust::xxh3::Xxh3,
+}
+
+impl<'a, F: WriteNoStd> ChecksumWriter<'a, F> {
+    /// Create a new [`ChecksumWriter`] on top of a generic [`WriteNoStd`] `F`.
+    pub fn new(backend: &'a mut F) -> Self {
+        Self {
+            backend,
+            hasher: xxhash_rust::xxh3::Xxh3::new(),
+        }
+    }
+
+    /// Return the checksum of the bytes written so far.
+    pub fn checksum(&self) -> u64 {
+        self.hasher.digest()
+    }
+}
+
+impl<F: WriteNoStd> WriteNoStd for ChecksumWriter<'_, F> {
+    #[inline(always)]
+    fn write_all(&mut self, buf: &[u8]) -> ser::Result<()> {
+        self.hasher.update(buf);
+        self.backend.write_all(buf)
+    }
+
+    #[inline(always)]
+    fn flush(&mut self) -> ser::Result<()> {
+        self.backend.flush()
+    }
 }
@@ -35,11 +35,13 @@ use super::*;
 /// which uses the default implementation, and [`SchemaWriter`],
 /// which additionally records a [`Schema`] of the serialized data.
 pub trait WriteWithNames: WriteWithPos + Sized {
-    /// Add some zero padding so that `self.pos() % V:max_size_of() == 0.`
+    /// Add some zero padding so that `self.pos() % V:max_size_of() == 0`, or
+    /// `self.pos() % min_align == 0` if the [minimum alignment](SerializeOptions::min_align)
+    /// is larger.
     ///
     /// Other implementations must write the same number of zeros.
     fn align<V: MaxSizeOf>(&mut self) -> Result<()> {
-        let padding = pad_align_to(self.pos(), V::max_size_of());
+        let padding = pad_align_to(self.pos(), V::max_size_of().max(self.options().min_align));
         for _ in 0..padding {
             self.write_all(&[0])?;
         }
@@ -183,6 +185,22 @@ impl<W: WriteWithPos> WriteWithPos for SchemaWriter<'_, W> {
     fn pos(&self) -> usize {
         self.writer.pos()
     }
+
+    fn options(&self) -> &SerializeOptions {
+        self.writer.options()
+    }
+
+    fn set_min_align(&mut self, min_align: usize) -> ser::Result<()> {
+        self.writer.set_min_align(min_align)
+    }
+
+    fn can_patch(&self) -> bool {
+        self.writer.can_patch()
+    }
+
+    fn patch(&mut self, pos: usize, buf: &[u8]) -> Result<()> {
+        self.writer.patch(pos, buf)
+    }
 }
 
 /// WARNING: these implementations must be kept in sync with the ones
@@ -190,7 +208,7 @@ impl<W: WriteWithPos> WriteWithPos for SchemaWriter<'_, W> {
 impl<W: WriteWithPos> WriteWithNames for SchemaWriter<'_, W> {
     #[inline(always)]
     fn align<T: MaxSizeOf>(&mut self) -> Result<()> {
-        let padding = pad_align_to(self.pos(), T::max_size_of());
+        let padding = pad_align_to(self.pos(), T::max_size_of().max(self.options().min_align));
         if padding != 0 {
             self.schema.0.push(SchemaRow {
                 field: "PADDING".into(),
@@ -16,20 +16,34 @@ use crate::prelude::MaxSizeOf;
 /// Internal trait used to select whether a type is zero-copy
 /// or deep-copy.
 ///
-/// It has only two implementations, [`Zero`] and [`Deep`].
+/// It has four implementations, [`Zero`], [`Deep`], [`Pool`], and [`Atomic`].
 ///
 /// In the first case, the type can be serialized
 /// from memory and deserialized to memory as a sequence of bytes;
 /// in the second case, one has to deserialize the type field
-/// by field.
+/// by field. The third case is a deep-copy type (a string) whose
+/// vectors and boxed slices are serialized as a [string pool](crate::deser::StrPool).
+/// The fourth case is an [atomic type](AtomicCopy), whose vectors and boxed
+/// slices are serialized as those of the underlying non-atomic type.
 pub trait CopySelector {
     const IS_ZERO_COPY: bool;
+    /// The conjunction of this selector and `S`: [`Zero`] if
+    /// both selectors are [`Zero`], [`Deep`] otherwise.
+    ///
+    /// It is used to compute the selector of composite types, such as tuples,
+    /// that are zero-copy if and only if all their components are.
+    /// The only exceptions are `Zero::And<Pool>`, which is [`Pool`], and
+    /// `Zero::And<Atomic>`, which is [`Atomic`]; thus,
+    /// `S::And<Zero>` is [`Zero`] or [`Deep`] for every selector `S`, and
+    /// composite types use it to normalize the selectors of their components.
+    type And<S: CopySelector>: CopySelector;
 }
 /// An implementation of a [`CopySelector`] specifying that a type is zero-copy.
 pub struct Zero {}
 
 impl CopySelector for Zero {
     const IS_ZERO_COPY: bool = true;
+    type And<S: CopySelector> = S;
 }
 
 /// An implementation of a [`CopySelector`] specifying that a type is deep-copy.
@@ -38,6 +52,26 @@ pub struct Deep {}
 
 impl CopySelector for Deep {
     const IS_ZERO_COPY: bool = false;
+    type And<S: CopySelector> = Deep;
+}
+
+/// An implementation of a [`CopySelector`] specifying that a type is deep-copy,
+/// but that vectors and boxed slices of the type are serialized as a
+/// [string pool](crate::deser::StrPool).
+pub struct Pool {}
+
+impl CopySelector for Pool {
+    const IS_ZERO_COPY: bool = false;
+    type And<S: CopySelector> = Deep;
+}
+
+/// An implementation of a [`CopySelector`] specifying that a type is an
+/// [atomic type](AtomicCopy).
+pub struct Atomic {}
+
+impl CopySelector for Atomic {
+    const IS_ZERO_COPY: bool = false;
+    type And<S: CopySelector> = Deep;
 }
 
 /**
@@ -75,8 +109,8 @@ using a reference to a slice of `T`.
 
 You can make zero-copy your own types, but you must ensure that they do not
 contain references and that they have a fixed memory layout; for structures, this requires
-`repr(C)`. ε-serde will track these conditions at compile time and check them at
-runtime: in case of failure, serialization will panic.
+`repr(C)`. ε-serde will track these conditions and check them at compile
+time: in case of failure, code serializing the type will not compile.
 
 Since we cannot use negative trait bounds, every type that is used as a parameter of
 an array, vector or boxed slice must implement either `CopySelector<Type=Zero>`
@@ -87,22 +121,118 @@ slice. If you do not implement either of these traits, the type will not be seri
 vectors or boxed slices but error messages will be very unhelpful due to the
 contrived way we have to implement mutually exclusive types.
 
+Strings are a special case: they are deep-copy, but their selector is
+[`Pool`], so that vectors and boxed slices of strings can be serialized as a
+[string pool](crate::deser::StrPool) and ε-copy deserialized without allocation.
+
 If you use the provided derive macros all this logic will be hidden from you. You'll
 just have to add `#[zero_copy]` to your structures (if you want them to be zero-copy)
 and ε-serde will do the rest.
 
 */
-
 pub trait CopyType: Sized {
     type Copy: CopySelector;
 }
 
+/// Trait specifying whether `Option` of a [copy type](CopyType) is zero-copy
+/// or deep-copy.
+///
+/// `Option<T>` is a copy type, and can be (de)serialized, only if `T`
+/// implements this trait. You should never implement this trait directly:
+/// it is implemented automatically, with `OptionCopy=Deep`, for all types
+/// that are not zero-copy, and by means of [`ZeroOptionCopyType`] for
+/// zero-copy types.
+pub trait OptionCopyType: CopyType {
+    /// [`Zero`] if `Option<Self>` is zero-copy, [`Deep`] otherwise.
+    ///
+    /// `Option<Self>` can be zero-copy only if `Self` is zero-copy and `None`
+    /// is represented by a niche of `Self` made of zero bytes, as it happens,
+    /// for example, for [non-zero integer types](core::num::NonZero).
+    /// In this case, `Option<Self>` has the same representation of `Self`,
+    /// and vectors and boxed slices of `Option<Self>` can be ε-copy deserialized
+    /// as slices.
+    type OptionCopy: CopySelector;
+}
+
+impl<T: CopyType + OptionCopyHelper<T::Copy>> OptionCopyType for T {
+    type OptionCopy = <T as OptionCopyHelper<T::Copy>>::OptionCopy;
+}
+
+/// Trait specifying whether `Option` of a [zero-copy type](ZeroCopy) is
+/// zero-copy or deep-copy.
+///
+/// All zero-copy types provided by ε-serde implement this trait, and the
+/// [derive macro](epserde_derive::Epserde) implements it, too; if you
+/// implement [`CopyType`] manually with `Copy=Zero`, you have to implement
+/// this trait, usually with `OptionCopy=Deep`, to use `Option` of your type.
+/// Types that are not zero-copy need not implement this trait.
+pub trait ZeroOptionCopyType: CopyType<Copy = Zero> {
+    /// The [`OptionCopyType::OptionCopy`] of this type.
+    ///
+    /// For types deriving [`Epserde`](epserde_derive::Epserde), this is [`Deep`]
+    /// unless the attribute `#[epserde(zero_copy_option)]` is specified.
+    /// Note that the language does not guarantee the position of niches in
+    /// user-defined structures: the condition is checked at compile time
+    /// when serializing or deserializing, so code using zero-copy options
+    /// might stop compiling with a future version of the compiler.
+    type OptionCopy: CopySelector;
+}
+
+/// Internal trait used to implement [`OptionCopyType`] depending on the
+/// [`CopySelector`] of a type, working around the lack of specialization
+/// in the same way of [`SerializeHelper`](crate::ser::SerializeHelper).
+pub trait OptionCopyHelper<S: CopySelector> {
+    type OptionCopy: CopySelector;
+}
+
+impl<T: ZeroOptionCopyType> OptionCopyHelper<Zero> for T {
+    type OptionCopy = <T as ZeroOptionCopyType>::OptionCopy;
+}
+
+impl<T> OptionCopyHelper<Deep> for T {
+    type OptionCopy = Deep;
+}
+
+impl<T> OptionCopyHelper<Pool> for T {
+    type OptionCopy = Deep;
+}
+
+impl<T> OptionCopyHelper<Atomic> for T {
+    type OptionCopy = Deep;
+}
+
 /// Marker trait for zero-copy types. You should never implement
 /// this trait directly, but rather implement [`CopyType`] with `Copy=Zero`.
 pub trait ZeroCopy: CopyType<Copy = Zero> + Copy + MaxSizeOf {}
 impl<T: CopyType<Copy = Zero> + Copy + MaxSizeOf> ZeroCopy for T {}
 
+/// Trait for atomic types, whose selector is [`Atomic`].
+///
+/// Atomic types are not zero-copy, as they are not [`Copy`], and accessing
+/// them through a shared reference would make it possible to modify
+/// read-only memory. Vectors and boxed slices of atomic types are serialized
+/// as those of the [non-atomic type](AtomicCopy::NonAtomic), and they are
+/// ε-copy deserialized as slices of the non-atomic type, but as slices
+/// of the atomic type by [`DeserializeMut`](crate::deser::DeserializeMut).
+///
+/// # Safety
+///
+/// The type must have the same size and representation of
+/// [`NonAtomic`](AtomicCopy::NonAtomic), and an alignment not larger than its size.
+pub unsafe trait AtomicCopy: CopyType<Copy = Atomic> + Sized {
+    /// The non-atomic type with the same representation.
+    type NonAtomic: ZeroCopy;
+    /// Create a new value.
+    fn new(value: Self::NonAtomic) -> Self;
+    /// Load the value with [relaxed](core::sync::atomic::Ordering::Relaxed) ordering.
+    fn load_relaxed(&self) -> Self::NonAtomic;
+}
+
 /// Marker trait for deep-copy types. You should never implement
 /// this trait directly, but rather implement [`CopyType`] with `Copy=Deep`.
+///
+/// Note that `String` and `Box<str>` are not `DeepCopy`, as their selector is
+/// [`Pool`]: a type parameter bounded by `DeepCopy` cannot be instantiated
+/// with strings.
 pub trait DeepCopy: CopyType<Copy = Deep> {}
 impl<T: CopyType<Copy = Deep>> DeepCopy for T {}
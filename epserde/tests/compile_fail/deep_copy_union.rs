@@ -0,0 +1,16 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use epserde::prelude::*;
+
+#[derive(Epserde)]
+#[repr(C)]
+union DeepUnion {
+    a: u32,
+    b: f32,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use epserde::prelude::*;
+
+#[derive(Epserde)]
+struct Data<A> {
+    a: A,
+    b: Vec<u64>,
+}
+
+fn main() {
+    let mut backend = epserde::new_aligned_cursor().into_inner();
+    // Mutations of b would be lost, as it is fully deserialized
+    let _ = <Data<Vec<u32>>>::deserialize_eps_mut(&mut backend);
+}
@@ -0,0 +1,17 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use epserde::prelude::*;
+
+#[derive(Epserde)]
+enum DuplicateTag {
+    #[epserde(tag_value = 1)]
+    A,
+    #[epserde(tag_value = 1)]
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use epserde::prelude::*;
+
+#[derive(Epserde)]
+#[repr(C)]
+#[zero_copy]
+struct DeepField {
+    a: Vec<u32>,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+#![deny(deprecated)]
+
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use epserde::prelude::*;
+
+#[derive(Epserde)]
+struct Undeclared {
+    a: u32,
+    b: u64,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+use epserde::prelude::*;
+
+#[derive(Epserde)]
+#[zero_copy]
+struct NotReprC {
+    a: u32,
+}
+
+fn main() {}
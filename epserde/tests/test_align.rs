@@ -0,0 +1,131 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use anyhow::Result;
+use epserde::prelude::*;
+
+#[derive(Epserde, Debug, PartialEq, Clone)]
+#[deep_copy]
+struct Aligned<A, B> {
+    small: u8,
+    #[epserde(align = 64)]
+    data: A,
+    #[epserde(align = 256)]
+    more: B,
+    #[epserde(align = 64)]
+    full: u16,
+}
+
+mod unaligned {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Debug, PartialEq, Clone)]
+    #[deep_copy]
+    pub struct Aligned<A, B> {
+        pub small: u8,
+        pub data: A,
+        pub more: B,
+        pub full: u16,
+    }
+}
+
+/// A block of memory aligned to 256 bytes.
+#[repr(C, align(256))]
+#[derive(Clone, Copy)]
+struct Block([u8; 256]);
+
+/// Copy `bytes` in memory aligned to 256 bytes.
+fn aligned(bytes: &[u8]) -> Vec<Block> {
+    let mut blocks = vec![Block([0; 256]); bytes.len().div_ceil(256)];
+    as_bytes_mut(&mut blocks)[..bytes.len()].copy_from_slice(bytes);
+    blocks
+}
+
+fn as_bytes(blocks: &[Block]) -> &[u8] {
+    // SAFETY: blocks are arrays of bytes
+    unsafe { core::slice::from_raw_parts(blocks.as_ptr() as *const u8, blocks.len() * 256) }
+}
+
+fn as_bytes_mut(blocks: &mut [Block]) -> &mut [u8] {
+    // SAFETY: blocks are arrays of bytes
+    unsafe { core::slice::from_raw_parts_mut(blocks.as_mut_ptr() as *mut u8, blocks.len() * 256) }
+}
+
+#[test]
+fn test_field_align() -> Result<()> {
+    let value = Aligned {
+        small: 1,
+        data: vec![1_u64, 2, 3],
+        more: vec![4_u8, 5],
+        full: 6,
+    };
+    let mut cursor = epserde::new_aligned_cursor();
+    value.serialize(&mut cursor)?;
+
+    let mut blocks = aligned(cursor.get_ref());
+    let backend = as_bytes(&blocks);
+    let eps = <Aligned<Vec<u64>, Vec<u8>>>::deserialize_eps(backend)?;
+    assert_eq!(eps.small, 1);
+    assert_eq!(eps.data, value.data.as_slice());
+    assert_eq!(eps.more, value.more.as_slice());
+    assert_eq!(eps.full, value.full);
+    assert_eq!(eps.data.as_ptr() as usize % 64, 0);
+    assert_eq!(eps.more.as_ptr() as usize % 256, 0);
+
+    let full = <Aligned<Vec<u64>, Vec<u8>>>::deserialize_full(&mut std::io::Cursor::new(backend))?;
+    assert_eq!(full, value);
+
+    // Memory aligned to 64, but not to 256 bytes is detected
+    let shifted = aligned(&[&[0; 64][..], cursor.get_ref()].concat());
+    assert!(matches!(
+        <Aligned<Vec<u64>, Vec<u8>>>::deserialize_eps(&as_bytes(&shifted)[64..]),
+        Err(deser::Error::AlignmentError)
+    ));
+
+    let eps = <Aligned<Vec<u64>, Vec<u8>>>::deserialize_eps_mut(as_bytes_mut(&mut blocks))?;
+    assert_eq!(eps.more.as_ptr() as usize % 256, 0);
+    eps.more[0] = 0;
+    let backend = as_bytes(&blocks);
+    assert_eq!(
+        <Aligned<Vec<u64>, Vec<u8>>>::deserialize_eps(backend)?.more,
+        [0, 5]
+    );
+
+    // Same type hash, different representation hash
+    assert!(matches!(
+        <unaligned::Aligned<Vec<u64>, Vec<u8>>>::deserialize_eps(backend),
+        Err(deser::Error::WrongTypeReprHash { .. })
+    ));
+
+    // Memory allocated by load_mem is suitably aligned
+    let mut file = std::env::temp_dir();
+    file.push(format!("test_field_align_{}.bin", std::process::id()));
+    value.store(&file)?;
+    let mem = <Aligned<Vec<u64>, Vec<u8>>>::load_mem(&file)?;
+    std::fs::remove_file(&file)?;
+    assert_eq!(mem.more, value.more.as_slice());
+    assert_eq!(mem.more.as_ptr() as usize % 256, 0);
+    Ok(())
+}
+
+#[test]
+fn test_load_mem_empty() -> Result<()> {
+    let mut file = std::env::temp_dir();
+    file.push(format!("test_load_mem_empty_{}.bin", std::process::id()));
+    std::fs::File::create(&file)?;
+    assert!(<Vec<u64>>::load_mem(&file).is_err());
+    std::fs::remove_file(&file)?;
+
+    let buffer = epserde::deser::AlignedBuffer::zeroed(0, epserde::deser::LOAD_MEM_ALIGN)?;
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.as_ptr() as usize % epserde::deser::LOAD_MEM_ALIGN, 0);
+    let buffer = epserde::deser::AlignedBuffer::zeroed(100, epserde::deser::LOAD_MEM_ALIGN)?;
+    assert_eq!(&*buffer, [0; 100]);
+    assert_eq!(buffer.as_ptr() as usize % epserde::deser::LOAD_MEM_ALIGN, 0);
+    Ok(())
+}
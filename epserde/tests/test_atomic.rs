@@ -0,0 +1,91 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use epserde::prelude::*;
+
+#[test]
+fn test_atomic_prim() {
+    let a = AtomicUsize::new(42);
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+
+    buf.set_position(0);
+    let full = AtomicUsize::deserialize_full(&mut buf).unwrap();
+    assert_eq!(full.load(Ordering::Relaxed), 42);
+
+    let bytes = buf.into_inner();
+    let eps = AtomicUsize::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.load(Ordering::Relaxed), 42);
+
+    let b = AtomicBool::new(true);
+    let mut buf = epserde::new_aligned_cursor();
+    b.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps = AtomicBool::deserialize_eps(&bytes).unwrap();
+    assert!(eps.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_atomic_vec() {
+    let v: Vec<AtomicU64> = (0..10).map(AtomicU64::new).collect();
+    let mut buf = epserde::new_aligned_cursor();
+    v.serialize(&mut buf).unwrap();
+
+    buf.set_position(0);
+    let full = <Vec<AtomicU64>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(full.len(), 10);
+    for (i, x) in full.iter().enumerate() {
+        assert_eq!(x.load(Ordering::Relaxed), i as u64);
+    }
+
+    // ε-copy deserialization returns a slice of the non-atomic type,
+    // as the backend might be read-only
+    let mut bytes = buf.into_inner();
+    let eps: &[u64] = <Vec<AtomicU64>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, (0..10).collect::<Vec<_>>());
+
+    let eps: &mut [AtomicU64] = <Vec<AtomicU64>>::deserialize_eps_mut(&mut bytes).unwrap();
+    eps[3].fetch_add(10, Ordering::Relaxed);
+    assert_eq!(<Vec<AtomicU64>>::deserialize_eps(&bytes).unwrap()[3], 13);
+
+    // Same layout of the non-atomic type
+    let mut buf = epserde::new_aligned_cursor();
+    let len = v.serialize_raw(&mut buf, 0).unwrap();
+    let mut prim_buf = epserde::new_aligned_cursor();
+    let prim = (0..10).collect::<Vec<u64>>();
+    assert_eq!(prim.serialize_raw(&mut prim_buf, 0).unwrap(), len);
+    assert_eq!(buf.get_ref()[..len], prim_buf.get_ref()[..len]);
+}
+
+#[test]
+fn test_atomic_mmap_mut() {
+    let v: Vec<AtomicU64> = (0..100).map(|_| AtomicU64::new(0)).collect();
+    let mut file = std::env::temp_dir();
+    // Unique per process, as test binaries can run concurrently
+    file.push(format!("test_atomic_mmap_mut_{}.bin", std::process::id()));
+    v.store(&file).unwrap();
+
+    let mem_case = <Vec<AtomicU64>>::mmap_mut(&file, Flags::empty()).unwrap();
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for x in mem_case.iter() {
+                    x.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    mem_case.flush().unwrap();
+    drop(mem_case);
+
+    let full = <Vec<AtomicU64>>::load_full(&file).unwrap();
+    assert!(full.iter().all(|x| x.load(Ordering::Relaxed) == 4));
+
+    std::fs::remove_file(file).unwrap();
+}
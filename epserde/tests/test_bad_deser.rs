@@ -23,7 +23,7 @@ fn test_wrong_endianess() {
             len,
         )
     };
-    assert!(v.as_ptr() as usize % 16 == 0, "{:p}", v.as_ptr());
+    assert!((v.as_ptr() as usize).is_multiple_of(16), "{:p}", v.as_ptr());
     let mut buf = std::io::Cursor::new(&mut v);
 
     let schema = data.serialize_with_schema(&mut buf).unwrap();
@@ -0,0 +1,128 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::deser::SetView;
+use epserde::prelude::*;
+use std::collections::{BTreeSet, BinaryHeap, HashSet, VecDeque};
+
+#[test]
+fn test_result() {
+    let a: Vec<Result<u32, String>> = vec![Ok(1), Err("error".to_string()), Ok(3)];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Vec<Result<u32, String>>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = <Vec<Result<u32, String>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, vec![Ok(1), Err("error"), Ok(3)]);
+
+    let a: Result<Vec<u64>, ()> = Ok(vec![1, 2, 3]);
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps = <Result<Vec<u64>, ()>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, Ok([1, 2, 3].as_slice()));
+}
+
+#[test]
+fn test_vec_deque() {
+    // Make the deque wrap around
+    let mut a = VecDeque::with_capacity(4);
+    a.extend([0_u32, 1, 2]);
+    a.pop_front();
+    a.extend([3, 4]);
+    assert!(!a.as_slices().1.is_empty());
+
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <VecDeque<u32>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps: &[u32] = <VecDeque<u32>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, [1, 2, 3, 4]);
+
+    let a: VecDeque<Vec<u8>> = VecDeque::from([vec![1], vec![2, 3]]);
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <VecDeque<Vec<u8>>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps: Vec<&[u8]> = <VecDeque<Vec<u8>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, [&[1][..], &[2, 3]]);
+}
+
+#[test]
+fn test_binary_heap() {
+    let a = BinaryHeap::from(vec![3_i64, 1, 4, 1, 5, 9, 2, 6]);
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <BinaryHeap<i64>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a.into_sorted_vec(), full.clone().into_sorted_vec());
+    assert_eq!(full.peek(), Some(&9));
+    let bytes = buf.into_inner();
+    let eps: &[i64] = <BinaryHeap<i64>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, full.as_slice());
+}
+
+#[test]
+fn test_btree_set() {
+    let a: BTreeSet<u16> = [10, 3, 7, 1].into_iter().collect();
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <BTreeSet<u16>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps: SetView<&[u16]> = <BTreeSet<u16>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(*eps, [1, 3, 7, 10]);
+    assert!(eps.contains(&7));
+    assert!(!eps.contains(&8));
+}
+
+#[test]
+fn test_hash_set() {
+    let a: HashSet<u64> = (0..100).map(|i| i * 7 % 101).collect();
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <HashSet<u64>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = <HashSet<u64>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.len(), 100);
+    assert!(eps.windows(2).all(|w| w[0] < w[1]));
+    for i in 0..101 {
+        assert_eq!(eps.contains(&i), a.contains(&i));
+    }
+
+    // Strings are stored in a sorted string pool
+    let a: HashSet<String> = ["foo", "bar", "baz"].map(String::from).into();
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <HashSet<String>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = <HashSet<String>>::deserialize_eps(&bytes).unwrap();
+    assert!(eps.iter().eq(["bar", "baz", "foo"]));
+    assert!(eps.contains("baz"));
+    assert!(!eps.contains("qux"));
+
+    // Deep-copy elements
+    let a: BTreeSet<Vec<u8>> = [vec![2], vec![1, 2], vec![]].into();
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps = <BTreeSet<Vec<u8>>>::deserialize_eps(&bytes).unwrap();
+    assert!(eps.contains(&[1, 2].as_slice()));
+    assert!(!eps.contains(&[1].as_slice()));
+}
@@ -0,0 +1,74 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use std::borrow::Cow;
+
+#[test]
+fn test_cow() {
+    let a: Cow<[u32]> = Cow::Owned(vec![1, 2, 3]);
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Cow<[u32]>>::deserialize_full(&mut buf).unwrap();
+    assert!(matches!(full, Cow::Owned(_)));
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = <Cow<[u32]>>::deserialize_eps(&bytes).unwrap();
+    assert!(matches!(eps, Cow::Borrowed(_)));
+    assert_eq!(a, eps);
+
+    let a: Cow<str> = Cow::Borrowed("ε-serde");
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Cow<str>>::deserialize_full(&mut buf).unwrap();
+    assert!(matches!(full, Cow::Owned(_)));
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = <Cow<str>>::deserialize_eps(&bytes).unwrap();
+    assert!(matches!(eps, Cow::Borrowed("ε-serde")));
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct Data<'a> {
+    name: Cow<'a, str>,
+    ids: Cow<'a, [u32]>,
+    count: usize,
+}
+
+impl Data<'_> {
+    fn total(&self) -> u32 {
+        self.ids.iter().sum()
+    }
+}
+
+#[test]
+fn test_lifetime_struct() {
+    let a = Data {
+        name: Cow::Owned("data".to_string()),
+        ids: Cow::Owned(vec![1, 2, 3, 4]),
+        count: 4,
+    };
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = Data::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+
+    let mut bytes = buf.into_inner();
+    // The same concrete type
+    let eps: Data = Data::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a, eps);
+    assert!(matches!(eps.ids, Cow::Borrowed(_)));
+    assert!(matches!(eps.name, Cow::Borrowed(_)));
+    assert_eq!(eps.total(), 10);
+
+    let eps_mut: Data = Data::deserialize_eps_mut(&mut bytes).unwrap();
+    assert_eq!(a, eps_mut);
+}
@@ -0,0 +1,77 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+
+#[derive(Epserde, Debug, PartialEq, Clone)]
+#[epserde(deser_struct)]
+struct Index {
+    offsets: Vec<u64>,
+    names: Vec<String>,
+    title: String,
+    count: usize,
+    #[epserde(skip)]
+    cache: Vec<u8>,
+}
+
+#[derive(Epserde, Debug, PartialEq, Clone)]
+#[epserde(deser_struct)]
+struct Wrapper<T>(T, Vec<u16>);
+
+#[test]
+fn test_deser_struct() {
+    let index = Index {
+        offsets: vec![0, 10, 20],
+        names: vec!["a".into(), "b".into()],
+        title: "index".into(),
+        count: 3,
+        cache: vec![1],
+    };
+    let mut cursor = epserde::new_aligned_cursor();
+    index.serialize(&mut cursor).unwrap();
+    cursor.set_position(0);
+    let full = Index::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(
+        full,
+        Index {
+            cache: vec![],
+            ..index.clone()
+        }
+    );
+
+    let mut bytes = cursor.into_inner();
+    let eps: IndexDeser = Index::deserialize_eps(&bytes).unwrap();
+    let offsets: &[u64] = eps.offsets;
+    assert_eq!(offsets, [0, 10, 20]);
+    assert_eq!(eps.names, index.names);
+    let title: &str = eps.title;
+    assert_eq!(title, "index");
+    assert_eq!(eps.count, 3);
+    assert!(eps.cache.is_empty());
+
+    let eps_mut: IndexDeserMut = Index::deserialize_eps_mut(&mut bytes).unwrap();
+    eps_mut.offsets[1] = 11;
+    let eps = Index::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.offsets, [0, 11, 20]);
+}
+
+#[test]
+fn test_deser_struct_generic() {
+    let w = Wrapper(vec![1_u32, 2], vec![3, 4]);
+    let mut cursor = epserde::new_aligned_cursor();
+    w.serialize(&mut cursor).unwrap();
+    cursor.set_position(0);
+    assert_eq!(
+        w,
+        <Wrapper<Vec<u32>>>::deserialize_full(&mut cursor).unwrap()
+    );
+    let bytes = cursor.into_inner();
+    let eps: WrapperDeser<Vec<u32>> = <Wrapper<Vec<u32>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.0, [1, 2]);
+    assert_eq!(eps.1, [3, 4]);
+}
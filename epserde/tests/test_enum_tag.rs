@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+
+mod v1 {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Debug, PartialEq)]
+    pub enum Event {
+        #[epserde(tag_value = 1)]
+        Start(u64),
+        #[epserde(tag_value = 2)]
+        Stop { code: i32 },
+        #[epserde(tag_value = 5)]
+        Message(String),
+    }
+}
+
+mod v2 {
+    use epserde::prelude::*;
+
+    // Same enum with variants in a different order
+    #[derive(Epserde, Debug, PartialEq)]
+    pub enum Event {
+        #[epserde(tag_value = 5)]
+        Message(String),
+        #[epserde(tag_value = 1)]
+        Start(u64),
+        #[epserde(tag_value = 2)]
+        Stop { code: i32 },
+    }
+}
+
+#[derive(Epserde, Debug, PartialEq, Clone, Copy)]
+#[deep_copy]
+enum Level {
+    Low = 3,
+    Medium,
+    High = 10,
+}
+
+#[derive(Epserde, Debug, PartialEq, Clone, Copy)]
+#[epserde(tag = "u32")]
+#[deep_copy]
+enum Wide {
+    A,
+    B,
+}
+
+fn to_bytes(value: &impl Serialize) -> Vec<u8> {
+    let mut cursor = epserde::new_aligned_cursor();
+    value.serialize(&mut cursor).unwrap();
+    cursor.into_inner().to_vec()
+}
+
+#[test]
+fn test_tag_width() {
+    // The tag is a u8, so it is the last byte
+    let bytes = to_bytes(&Level::Medium);
+    assert_eq!(bytes.last(), Some(&4));
+    assert_eq!(
+        *Level::deserialize_eps(&bytes).as_ref().unwrap(),
+        Level::Medium
+    );
+    assert_eq!(to_bytes(&Level::High).last(), Some(&10));
+    assert_eq!(to_bytes(&Level::Low).len(), bytes.len());
+
+    let bytes = to_bytes(&Wide::B);
+    assert_eq!(bytes[bytes.len() - 4..], 1_u32.to_ne_bytes());
+    assert_eq!(Wide::deserialize_eps(&bytes).unwrap(), Wide::B);
+}
+
+#[test]
+fn test_invalid_tag() {
+    let mut bytes = to_bytes(&Level::High);
+    *bytes.last_mut().unwrap() = 5;
+    assert!(matches!(
+        Level::deserialize_eps(&bytes),
+        Err(epserde::deser::Error::InvalidTag(5))
+    ));
+}
+
+#[test]
+fn test_reordering() {
+    for (a, b) in [
+        (v1::Event::Start(7), v2::Event::Start(7)),
+        (v1::Event::Stop { code: -1 }, v2::Event::Stop { code: -1 }),
+        (
+            v1::Event::Message("hello".into()),
+            v2::Event::Message("hello".into()),
+        ),
+    ] {
+        let bytes = to_bytes(&a);
+        let mut cursor = std::io::Cursor::new(&bytes);
+        assert_eq!(v2::Event::deserialize_full(&mut cursor).unwrap(), b);
+        // Only the type names in the headers differ
+        assert_eq!(bytes.len(), to_bytes(&b).len());
+    }
+}
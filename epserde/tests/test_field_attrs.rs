@@ -0,0 +1,163 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use std::hash::Hasher;
+use std::net::Ipv4Addr;
+use xxhash_rust::xxh3::Xxh3;
+
+/// Serialize an address as a `u32`.
+mod ipv4 {
+    use epserde::prelude::*;
+    use std::net::Ipv4Addr;
+
+    pub fn serialize(
+        addr: &Ipv4Addr,
+        backend: &mut impl epserde::ser::WriteWithNames,
+    ) -> epserde::ser::Result<()> {
+        backend.write("addr", &u32::from(*addr))
+    }
+
+    pub fn deserialize(
+        backend: &mut impl epserde::deser::ReadWithPos,
+    ) -> epserde::deser::Result<Ipv4Addr> {
+        Ok(Ipv4Addr::from(u32::_deserialize_full_inner(backend)?))
+    }
+}
+
+/// Serialize an address as a big-endian `u32`.
+mod ipv4_be {
+    use epserde::prelude::*;
+    use std::net::Ipv4Addr;
+
+    pub fn serialize(
+        addr: &Ipv4Addr,
+        backend: &mut impl epserde::ser::WriteWithNames,
+    ) -> epserde::ser::Result<()> {
+        backend.write("addr", &u32::from(*addr).to_be())
+    }
+
+    pub fn deserialize(
+        backend: &mut impl epserde::deser::ReadWithPos,
+    ) -> epserde::deser::Result<Ipv4Addr> {
+        Ok(Ipv4Addr::from(u32::from_be(u32::_deserialize_full_inner(
+            backend,
+        )?)))
+    }
+}
+
+fn answer() -> u64 {
+    42
+}
+
+#[derive(Epserde, Debug, PartialEq, Clone)]
+struct Data {
+    ids: Vec<u32>,
+    #[epserde(skip)]
+    cache: Vec<u64>,
+    #[epserde(skip, default = "answer")]
+    answer: u64,
+    #[epserde(with = "ipv4")]
+    addr: Ipv4Addr,
+    name: String,
+}
+
+mod renamed {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Debug, PartialEq, Clone)]
+    pub struct Data {
+        pub ids: Vec<u32>,
+        #[epserde(skip)]
+        pub cache: Vec<u64>,
+        #[epserde(skip, default = "super::answer")]
+        pub answer: u64,
+        #[epserde(with = "super::ipv4")]
+        pub addr: std::net::Ipv4Addr,
+        #[epserde(rename = "name")]
+        pub label: String,
+    }
+}
+
+mod other_module {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Debug, PartialEq, Clone)]
+    pub struct Data {
+        pub ids: Vec<u32>,
+        #[epserde(skip)]
+        pub cache: Vec<u64>,
+        #[epserde(skip)]
+        pub answer: u64,
+        #[epserde(with = "super::ipv4_be")]
+        pub addr: std::net::Ipv4Addr,
+        pub name: String,
+    }
+}
+
+mod reordered {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Debug, PartialEq, Clone)]
+    pub struct Data {
+        #[epserde(with = "super::ipv4")]
+        pub addr: std::net::Ipv4Addr,
+        pub ids: Vec<u32>,
+        #[epserde(skip)]
+        pub cache: Vec<u64>,
+        #[epserde(skip)]
+        pub answer: u64,
+        pub name: String,
+    }
+}
+
+fn type_hash<T: TypeHash>() -> u64 {
+    let mut hasher = Xxh3::with_seed(0);
+    T::type_hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_field_attrs() {
+    let data = Data {
+        ids: vec![1, 2, 3],
+        cache: vec![4, 5],
+        answer: 0,
+        addr: Ipv4Addr::new(192, 168, 0, 1),
+        name: "data".into(),
+    };
+    let mut cursor = epserde::new_aligned_cursor();
+    data.serialize(&mut cursor).unwrap();
+    cursor.set_position(0);
+    let full = Data::deserialize_full(&mut cursor).unwrap();
+    let expected = Data {
+        cache: vec![],
+        answer: 42,
+        ..data.clone()
+    };
+    assert_eq!(full, expected);
+
+    let bytes = cursor.into_inner();
+    let eps = Data::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.ids, [1, 2, 3]);
+    assert!(eps.cache.is_empty());
+    assert_eq!(eps.answer, 42);
+    assert_eq!(eps.addr, data.addr);
+    assert_eq!(eps.name, "data");
+
+    // Renaming a field does not change the type hash
+    assert_eq!(type_hash::<Data>(), type_hash::<renamed::Data>());
+    let mut cursor = std::io::Cursor::new(&bytes);
+    let renamed = renamed::Data::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(renamed.label, "data");
+
+    // Fields with custom serialization are hashed in declaration order,
+    // together with the module serializing them
+    assert_ne!(type_hash::<Data>(), type_hash::<other_module::Data>());
+    assert_ne!(type_hash::<Data>(), type_hash::<reordered::Data>());
+}
@@ -68,3 +68,158 @@ fn test_inner_param_eps() {
     let eps = <Data2<usize, Vec<usize>>>::deserialize_eps(&bytes).unwrap();
     assert_eq!(data.a, eps.a);
 }
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct Data3<B>
+where
+    B: AsRef<[u64]>,
+{
+    a: B,
+}
+
+impl<B> Data3<B>
+where
+    B: AsRef<[u64]>,
+{
+    fn sum(&self) -> u64 {
+        self.a.as_ref().iter().sum()
+    }
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+enum Data4<B>
+where
+    B: AsRef<[u64]>,
+{
+    A(B),
+    B,
+}
+
+#[test]
+fn test_where_clause() {
+    let data = Data3 {
+        a: vec![1_u64, 2, 3],
+    };
+
+    let mut buf = epserde::new_aligned_cursor();
+    let _bytes_written = data.serialize(&mut buf).unwrap();
+
+    buf.set_position(0);
+    let full = <Data3<Vec<u64>>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(data, full);
+
+    let bytes = buf.into_inner();
+    // The bound in the where clause holds for the deserialization type
+    let eps = <Data3<Vec<u64>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.sum(), 6);
+
+    let data = Data4::A(vec![1_u64, 2, 3]);
+
+    let mut buf = epserde::new_aligned_cursor();
+    let _bytes_written = data.serialize(&mut buf).unwrap();
+
+    buf.set_position(0);
+    let full = <Data4<Vec<u64>>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(data, full);
+
+    let bytes = buf.into_inner();
+    let eps = <Data4<Vec<u64>>>::deserialize_eps(&bytes).unwrap();
+    match eps {
+        Data4::A(a) => assert_eq!(a, [1, 2, 3]),
+        Data4::B => panic!(),
+    }
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+#[epserde(nested_params)]
+struct Nested<T> {
+    data: Vec<T>,
+    first: Option<T>,
+    pair: [T; 2],
+    id: usize,
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+#[epserde(nested_params)]
+enum NestedEnum<T> {
+    Many(Vec<T>),
+    Pairs { pairs: Vec<(T, T)> },
+}
+
+#[test]
+fn test_nested_params() {
+    let data = Nested {
+        data: vec![vec![1_u32, 2], vec![3]],
+        first: Some(vec![4, 5]),
+        pair: [vec![6], vec![7, 8]],
+        id: 9,
+    };
+
+    let mut buf = epserde::new_aligned_cursor();
+    let _bytes_written = data.serialize(&mut buf).unwrap();
+
+    buf.set_position(0);
+    let full = <Nested<Vec<u32>>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(data, full);
+
+    // Type parameters nested in fields are replaced by their deserialization types
+    let mut bytes = buf.into_inner();
+    let eps: Nested<&[u32]> = <Nested<Vec<u32>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.data, vec![&[1, 2][..], &[3]]);
+    assert_eq!(eps.first, Some(&[4, 5][..]));
+    assert_eq!(eps.pair, [&[6][..], &[7, 8]]);
+    assert_eq!(eps.id, 9);
+
+    let mut eps: Nested<&mut [u32]> = <Nested<Vec<u32>>>::deserialize_eps_mut(&mut bytes).unwrap();
+    eps.data[1][0] = 10;
+    let eps = <Nested<Vec<u32>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.data[1], &[10]);
+
+    let data = NestedEnum::Pairs {
+        pairs: vec![(vec![1_u32], vec![2, 3])],
+    };
+
+    let mut buf = epserde::new_aligned_cursor();
+    let _bytes_written = data.serialize(&mut buf).unwrap();
+
+    buf.set_position(0);
+    let full = <NestedEnum<Vec<u32>>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(data, full);
+
+    let bytes = buf.into_inner();
+    let eps: NestedEnum<&[u32]> = <NestedEnum<Vec<u32>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(
+        eps,
+        NestedEnum::Pairs {
+            pairs: vec![(&[1][..], &[2, 3][..])]
+        }
+    );
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct Internal<T> {
+    v: Vec<T>,
+    x: usize,
+}
+
+#[test]
+fn test_internal_zero_copy_param() {
+    // Without nested_params, internal parameters are left untouched,
+    // so they can be zero-copy
+    let data = Internal {
+        v: vec![1_u32, 2, 3],
+        x: 4,
+    };
+
+    let mut buf = epserde::new_aligned_cursor();
+    let _bytes_written = data.serialize(&mut buf).unwrap();
+
+    buf.set_position(0);
+    let full = <Internal<u32>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(data, full);
+
+    let bytes = buf.into_inner();
+    let eps: Internal<u32> = <Internal<u32>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(data, eps);
+    // Internal does not implement DeserializeMut, as mutations of v would be lost
+}
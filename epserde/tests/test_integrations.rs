@@ -0,0 +1,197 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+#[allow(unused_imports)]
+use epserde::prelude::*;
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn test_ndarray() {
+    use ndarray::{array, s, Array1, Array2, ArrayD, IxDyn};
+
+    let a: Array2<u32> = array![[1, 2, 3], [4, 5, 6]];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Array2<u32>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let mut bytes = buf.into_inner();
+    let eps = <Array2<u32>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a.view(), eps);
+    let mut eps_mut = <Array2<u32>>::deserialize_eps_mut(&mut bytes).unwrap();
+    eps_mut[[1, 2]] = 7;
+    let eps = <Array2<u32>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps[[1, 2]], 7);
+
+    // Fortran order is preserved
+    let a = a.reversed_axes();
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps = <Array2<u32>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a.view(), eps);
+    assert_eq!(a.strides(), eps.strides());
+
+    // Non-contiguous arrays are written in standard order
+    let mut a = Array1::from_iter((0..10).map(|x| x as f64));
+    a.slice_collapse(s![..;3]);
+    assert!(a.as_slice_memory_order().is_none());
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    assert_eq!(a, <Array1<f64>>::deserialize_full(&mut buf).unwrap());
+    let bytes = buf.into_inner();
+    let eps = <Array1<f64>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a.view(), eps);
+    assert_eq!(eps.strides(), &[1]);
+
+    let a = ArrayD::<u8>::from_shape_fn(IxDyn(&[2, 3, 4]), |d| (d[0] * 12 + d[1] * 4 + d[2]) as u8);
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps = <ArrayD<u8>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a.view(), eps);
+
+    // The number of dimensions is part of the type hash
+    assert!(<ndarray::Array3<u8>>::deserialize_eps(&bytes).is_err());
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn test_half() {
+    use half::{bf16, f16};
+
+    let v = vec![f16::from_f32(1.5), f16::from_f32(-2.25)];
+    let mut buf = epserde::new_aligned_cursor();
+    v.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps = <Vec<f16>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(v.as_slice(), eps);
+
+    let x = bf16::from_f32(3.0);
+    let mut buf = epserde::new_aligned_cursor();
+    x.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    assert_eq!(x, bf16::deserialize_full(&mut buf).unwrap());
+}
+
+#[cfg(feature = "bitvec")]
+#[test]
+fn test_bitvec() {
+    use bitvec::prelude::*;
+
+    let mut a: BitVec<u64, Lsb0> = BitVec::new();
+    for i in 0..1000 {
+        a.push(i % 3 == 0);
+    }
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <BitVec<u64, Lsb0>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let mut bytes = buf.into_inner();
+    let eps = <BitVec<u64, Lsb0>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a.as_bitslice(), eps);
+    let eps_mut = <BitVec<u64, Lsb0>>::deserialize_eps_mut(&mut bytes).unwrap();
+    eps_mut.set(1, true);
+    let eps = <BitVec<u64, Lsb0>>::deserialize_eps(&bytes).unwrap();
+    assert!(eps[1]);
+
+    // A bit vector that does not start at the first bit of its storage
+    let b: BitVec<u8, Msb0> = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0, 1, 1];
+    let c = b[3..].to_bitvec();
+    let mut d: BitVec<u8, Msb0> = b.clone();
+    d.drain(..3);
+    assert_eq!(c, d);
+    let mut buf = epserde::new_aligned_cursor();
+    d.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <BitVec<u8, Msb0>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(c, full);
+    let bytes = buf.into_inner();
+    let eps = <BitVec<u8, Msb0>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(c.as_bitslice(), eps);
+
+    // Bit order is part of the type hash
+    assert!(<BitVec<u8, Lsb0>>::deserialize_eps(&bytes).is_err());
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_smallvec() {
+    use smallvec::{smallvec, SmallVec};
+
+    let a: SmallVec<[u32; 4]> = smallvec![1, 2, 3];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <SmallVec<[u32; 4]>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    assert!(!full.spilled());
+    let bytes = buf.into_inner();
+    let eps = <SmallVec<[u32; 4]>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a.as_slice(), eps);
+    let a: SmallVec<[String; 2]> = smallvec!["a".into(), "bb".into(), "ccc".into()];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <SmallVec<[String; 2]>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = <SmallVec<[String; 2]>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, a.to_vec());
+}
+
+#[cfg(feature = "arrayvec")]
+#[test]
+fn test_arrayvec() {
+    use arrayvec::ArrayVec;
+
+    let a: ArrayVec<u16, 8> = (0..5).collect();
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <ArrayVec<u16, 8>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    buf.set_position(0);
+    // Too many elements for the capacity
+    assert!(<ArrayVec<u16, 4>>::deserialize_full(&mut buf).is_err());
+    let bytes = buf.into_inner();
+    let eps = <ArrayVec<u16, 8>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a.as_slice(), eps);
+}
+
+#[cfg(feature = "tinyvec")]
+#[test]
+fn test_tinyvec() {
+    use tinyvec::{array_vec, tiny_vec, ArrayVec, TinyVec};
+
+    let a: TinyVec<[u64; 2]> = tiny_vec![1, 2, 3, 4];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <TinyVec<[u64; 2]>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = <TinyVec<[u64; 2]>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a.as_slice(), eps);
+
+    let a: ArrayVec<[i8; 4]> = array_vec![-1, 0, 1];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <ArrayVec<[i8; 4]>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    buf.set_position(0);
+    assert!(<ArrayVec<[i8; 2]>>::deserialize_full(&mut buf).is_err());
+    let mut bytes = buf.into_inner();
+    let eps_mut = <ArrayVec<[i8; 4]>>::deserialize_eps_mut(&mut bytes).unwrap();
+    eps_mut[0] = 5;
+    let eps = <ArrayVec<[i8; 4]>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, &[5, 0, 1]);
+}
@@ -0,0 +1,124 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use anyhow::Result;
+use epserde::impls::iter::SerIter;
+use epserde::prelude::*;
+use epserde::ser::SerializeOptions;
+
+#[derive(Epserde, Debug, PartialEq, Clone)]
+struct Data<A> {
+    a: A,
+    b: Vec<u64>,
+}
+
+#[test]
+fn test_iter_zero() -> Result<()> {
+    let data = (0..10_000_u64).map(|x| x * 3).collect::<Vec<_>>();
+    // Same layout as the vector (the header contains the type name)
+    let mut cursor = epserde::new_aligned_cursor();
+    let len = SerIter::new(data.iter().copied()).serialize_raw(&mut cursor, 0)?;
+    let mut vec_cursor = epserde::new_aligned_cursor();
+    assert_eq!(data.serialize_raw(&mut vec_cursor, 0)?, len);
+    assert_eq!(cursor.get_ref()[..len], vec_cursor.get_ref()[..len]);
+
+    let mut cursor = epserde::new_aligned_cursor();
+    SerIter::new(data.iter().copied()).serialize(&mut cursor)?;
+    let backend = cursor.into_inner();
+    assert_eq!(<Vec<u64>>::deserialize_eps(&backend)?, data.as_slice());
+    Ok(())
+}
+
+#[test]
+fn test_iter_deep() -> Result<()> {
+    let data = vec![vec![1_u32], vec![], vec![2, 3]];
+    let mut cursor = epserde::new_aligned_cursor();
+    SerIter::new(data.clone()).serialize(&mut cursor)?;
+    cursor.set_position(0);
+    assert_eq!(<Vec<Vec<u32>>>::deserialize_full(&mut cursor)?, data);
+
+    let strings = ["a", "bc", "def"];
+    let mut cursor = epserde::new_aligned_cursor();
+    SerIter::new(strings.iter().map(|s| s.to_string())).serialize(&mut cursor)?;
+    let backend = cursor.into_inner();
+    let pool = <Vec<String>>::deserialize_eps(&backend)?;
+    assert_eq!(pool.iter().collect::<Vec<_>>(), strings);
+    Ok(())
+}
+
+#[test]
+fn test_iter_field() -> Result<()> {
+    let data = Data {
+        a: SerIter::new(0..100_u32),
+        b: vec![4, 5],
+    };
+    let mut cursor = epserde::new_aligned_cursor();
+    data.serialize(&mut cursor)?;
+    let backend = cursor.into_inner();
+    let eps = <Data<Vec<u32>>>::deserialize_eps(&backend)?;
+    assert_eq!(eps.a, (0..100).collect::<Vec<_>>());
+    assert_eq!(eps.b, [4, 5]);
+    Ok(())
+}
+
+#[test]
+fn test_iter_unknown_len() -> Result<()> {
+    let data = Data {
+        a: SerIter::new((0..1000_u16).filter(|x| x % 7 == 0)),
+        b: vec![4, 5],
+    };
+    let mut cursor = epserde::new_aligned_cursor();
+    assert!(matches!(
+        data.serialize(&mut cursor),
+        Err(ser::Error::UnknownLength)
+    ));
+
+    let data = Data {
+        a: SerIter::new((0..1000_u16).filter(|x| x % 7 == 0)),
+        b: vec![4, 5],
+    };
+    let mut cursor = epserde::new_aligned_cursor();
+    let len = data.serialize_with_seek(&SerializeOptions::new(), &mut cursor)?;
+    assert_eq!(cursor.position() as usize, len);
+    let backend = cursor.into_inner();
+    let eps = <Data<Vec<u16>>>::deserialize_eps(&backend)?;
+    assert_eq!(eps.a, (0..1000).step_by(7).collect::<Vec<_>>());
+    assert_eq!(eps.b, [4, 5]);
+
+    let mut cursor = epserde::new_aligned_cursor();
+    SerIter::new([vec![1_u8], vec![2, 3]].into_iter().flatten())
+        .serialize_with_seek(&SerializeOptions::new(), &mut cursor)?;
+    cursor.set_position(0);
+    assert_eq!(<Vec<u8>>::deserialize_full(&mut cursor)?, [1, 2, 3]);
+    Ok(())
+}
+
+/// An iterator lying about its length
+struct Liar(core::ops::Range<u64>);
+
+impl Iterator for Liar {
+    type Item = u64;
+    fn next(&mut self) -> Option<u64> {
+        self.0.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (10, Some(10))
+    }
+}
+
+#[test]
+fn test_iter_len_mismatch() {
+    let mut cursor = epserde::new_aligned_cursor();
+    assert!(matches!(
+        SerIter::new(Liar(0..5)).serialize(&mut cursor),
+        Err(ser::Error::IteratorLengthMismatch {
+            expected: 10,
+            actual: 5
+        })
+    ));
+}
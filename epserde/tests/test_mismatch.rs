@@ -0,0 +1,65 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use epserde::ser::{set_mismatch_hook, set_mismatch_policy, MismatchPolicy};
+use std::sync::Mutex;
+
+// Parameters make the compile-time lint bail out, leaving
+// the mismatch to the runtime check.
+#[derive(Epserde, Debug, PartialEq, Clone, Copy)]
+struct Mismatch<T> {
+    a: T,
+    b: T,
+}
+
+#[derive(Epserde, Debug, PartialEq, Clone, Copy)]
+#[deep_copy]
+struct Declared<T> {
+    a: T,
+}
+
+static REPORTED: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+fn record(type_name: &'static str) {
+    REPORTED.lock().unwrap().push(type_name);
+}
+
+// The policy and the hook are global, so we test them in a single function.
+#[test]
+fn test_mismatch() {
+    set_mismatch_hook(record);
+
+    let v = vec![Mismatch { a: 0_u32, b: 1 }; 10];
+    let d = vec![Declared { a: 0_u32 }; 10];
+    let mut buf = epserde::new_aligned_cursor();
+    v.serialize(&mut buf).unwrap();
+    v.serialize(&mut buf).unwrap();
+    d.serialize(&mut buf).unwrap();
+    // Reported once
+    assert_eq!(
+        *REPORTED.lock().unwrap(),
+        vec![core::any::type_name::<Mismatch<u32>>()]
+    );
+
+    set_mismatch_policy(MismatchPolicy::Strict);
+    let mut buf = epserde::new_aligned_cursor();
+    assert!(matches!(
+        v.serialize(&mut buf),
+        Err(ser::Error::ZeroCopyMismatch(type_name))
+            if type_name == core::any::type_name::<Mismatch<u32>>()
+    ));
+    d.serialize(&mut buf).unwrap();
+
+    set_mismatch_policy(MismatchPolicy::Ignore);
+    let mut buf = epserde::new_aligned_cursor();
+    Mismatch { a: 0_u64, b: 1 }.serialize(&mut buf).unwrap();
+    assert_eq!(REPORTED.lock().unwrap().len(), 1);
+
+    set_mismatch_policy(MismatchPolicy::Warn);
+}
@@ -0,0 +1,78 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+struct Point {
+    x: usize,
+    y: usize,
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct Data<A, B, C> {
+    counters: A,
+    points: B,
+    origin: C,
+    id: usize,
+}
+
+type Struct = Data<Vec<u64>, Vec<Point>, Point>;
+
+#[test]
+fn test_deserialize_eps_mut() {
+    let data = Struct {
+        counters: vec![0, 1, 2, 3],
+        points: vec![Point { x: 0, y: 1 }, Point { x: 2, y: 3 }],
+        origin: Point { x: 4, y: 5 },
+        id: 6,
+    };
+    let mut cursor = epserde::new_aligned_cursor();
+    data.serialize(&mut cursor).unwrap();
+    let mut bytes = cursor.into_inner();
+
+    let eps = Struct::deserialize_eps_mut(&mut bytes).unwrap();
+    eps.counters[2] += 10;
+    eps.points[1].x = 7;
+    eps.origin.y = 8;
+
+    let eps = Struct::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.counters, &[0, 1, 12, 3]);
+    assert_eq!(eps.points, &[Point { x: 0, y: 1 }, Point { x: 7, y: 3 }]);
+    assert_eq!(*eps.origin, Point { x: 4, y: 8 });
+    assert_eq!(eps.id, 6);
+}
+
+#[test]
+fn test_mmap_mut() {
+    let data = Struct {
+        counters: vec![0; 100],
+        points: vec![Point { x: 0, y: 0 }; 10],
+        origin: Point { x: 0, y: 0 },
+        id: 0,
+    };
+    let mut file = std::env::temp_dir();
+    // Unique per process, as test binaries can run concurrently
+    file.push(format!("test_mmap_mut_{}.bin", std::process::id()));
+    data.store(&file).unwrap();
+
+    let mut mem_case = Struct::mmap_mut(&file, Flags::empty()).unwrap();
+    mem_case.counters.iter_mut().for_each(|x| *x += 1);
+    mem_case.points[9] = Point { x: 1, y: 2 };
+    mem_case.flush().unwrap();
+    drop(mem_case);
+
+    let full = Struct::load_full(&file).unwrap();
+    assert_eq!(full.counters, vec![1; 100]);
+    assert_eq!(full.points[9], Point { x: 1, y: 2 });
+    assert_eq!(full.points[..9], vec![Point { x: 0, y: 0 }; 9]);
+
+    std::fs::remove_file(file).unwrap();
+}
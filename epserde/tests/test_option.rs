@@ -0,0 +1,141 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use core::num::{NonZeroU32, NonZeroU64};
+use epserde::prelude::*;
+
+#[test]
+fn test_option_deep() {
+    for a in [None, Some(vec![1_usize, 2, 3])] {
+        let mut buf = epserde::new_aligned_cursor();
+        a.serialize(&mut buf).unwrap();
+        buf.set_position(0);
+        let full = <Option<Vec<usize>>>::deserialize_full(&mut buf).unwrap();
+        assert_eq!(a, full);
+        let bytes = buf.into_inner();
+        let eps = <Option<Vec<usize>>>::deserialize_eps(&bytes).unwrap();
+        assert_eq!(a.as_deref(), eps);
+    }
+
+    let a = vec![None, Some(1_u32), Some(2)];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps = <Vec<Option<u32>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a, eps);
+}
+
+#[test]
+fn test_option_nonzero() {
+    for a in [None, NonZeroU64::new(42)] {
+        let mut buf = epserde::new_aligned_cursor();
+        a.serialize(&mut buf).unwrap();
+        buf.set_position(0);
+        let full = <Option<NonZeroU64>>::deserialize_full(&mut buf).unwrap();
+        assert_eq!(a, full);
+        let bytes = buf.into_inner();
+        let eps = <Option<NonZeroU64>>::deserialize_eps(&bytes).unwrap();
+        assert_eq!(a, eps);
+    }
+}
+
+#[test]
+fn test_vec_option_nonzero() {
+    let a: Vec<Option<NonZeroU64>> = (0..100).map(NonZeroU64::new).collect();
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Vec<Option<NonZeroU64>>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps: &[Option<NonZeroU64>] = <Vec<Option<NonZeroU64>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a, eps);
+
+    // Same layout as the underlying integers
+    let b: Vec<u64> = (0..100).collect();
+    let mut buf = epserde::new_aligned_cursor();
+    b.serialize(&mut buf).unwrap();
+    let b_bytes = buf.into_inner();
+    assert_eq!(bytes[bytes.len() - 800..], b_bytes[b_bytes.len() - 800..]);
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+#[epserde(zero_copy_option)]
+struct Id(NonZeroU32);
+
+#[test]
+fn test_option_derive() {
+    let a = vec![Some(Id(NonZeroU32::MIN)), None, Some(Id(NonZeroU32::MAX))];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Vec<Option<Id>>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps: &[Option<Id>] = <Vec<Option<Id>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a, eps);
+}
+
+/// A deep-copy type with manual implementations, which does not need to
+/// implement `ZeroOptionCopyType` to be used in an `Option`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Meters(u64);
+
+impl CopyType for Meters {
+    type Copy = Deep;
+}
+
+impl TypeHash for Meters {
+    fn type_hash(hasher: &mut impl core::hash::Hasher) {
+        use core::hash::Hash;
+        "Meters".hash(hasher);
+    }
+}
+
+impl ReprHash for Meters {
+    fn repr_hash(_hasher: &mut impl core::hash::Hasher, _offset_of: &mut usize) {}
+}
+
+impl ser::SerializeInner for Meters {
+    const IS_ZERO_COPY: bool = false;
+    const ZERO_COPY_MISMATCH: bool = false;
+
+    fn _serialize_inner(&self, backend: &mut impl ser::WriteWithNames) -> ser::Result<()> {
+        backend.write("meters", &self.0)
+    }
+}
+
+impl DeserializeInner for Meters {
+    type DeserType<'a> = Self;
+
+    fn _deserialize_full_inner(backend: &mut impl ReadWithPos) -> deser::Result<Self> {
+        Ok(Meters(u64::_deserialize_full_inner(backend)?))
+    }
+
+    fn _deserialize_eps_inner<'a>(
+        backend: &mut SliceWithPos<'a>,
+    ) -> deser::Result<Self::DeserType<'a>> {
+        Ok(Meters(u64::_deserialize_eps_inner(backend)?))
+    }
+}
+
+#[test]
+fn test_option_manual_deep() {
+    for a in [Some(Meters(42)), None] {
+        let mut buf = epserde::new_aligned_cursor();
+        a.serialize(&mut buf).unwrap();
+        buf.set_position(0);
+        let full = <Option<Meters>>::deserialize_full(&mut buf).unwrap();
+        assert_eq!(a, full);
+        let bytes = buf.into_inner();
+        let eps = <Option<Meters>>::deserialize_eps(&bytes).unwrap();
+        assert_eq!(a, eps);
+    }
+}
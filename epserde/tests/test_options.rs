@@ -0,0 +1,186 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use anyhow::Result;
+use epserde::deser::{DeserializeOptions, Validation};
+use epserde::prelude::*;
+use epserde::ser::{MismatchPolicy, SerializeOptions};
+
+#[derive(Epserde, Debug, PartialEq, Clone)]
+struct Data<A> {
+    a: A,
+    b: u64,
+}
+
+#[test]
+fn test_no_header() -> Result<()> {
+    let data = Data {
+        a: vec![1_u32, 2, 3],
+        b: 4,
+    };
+    let mut cursor = epserde::new_aligned_cursor();
+    let len = data.serialize_with(&SerializeOptions::new().header(false), &mut cursor)?;
+
+    let mut full = epserde::new_aligned_cursor();
+    assert!(data.serialize(&mut full)? > len);
+
+    let options = DeserializeOptions::new().header(false);
+    cursor.set_position(0);
+    let full = <Data<Vec<u32>>>::deserialize_full_with(&options, &mut cursor)?;
+    assert_eq!(full, data);
+    let mut backend = cursor.into_inner();
+    let eps = <Data<Vec<u32>>>::deserialize_eps_with(&options, &backend)?;
+    assert_eq!(eps.a, data.a.as_slice());
+    assert_eq!(eps.b, data.b);
+    let eps = <Data<Vec<u32>>>::deserialize_eps_mut_with(&options, &mut backend)?;
+    assert_eq!(eps.a, data.a.as_slice());
+
+    assert!(matches!(
+        <Data<Vec<u32>>>::deserialize_eps(&backend),
+        Err(deser::Error::MagicCookieError(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_min_align() -> Result<()> {
+    let data = vec![1_u16, 2, 3];
+    let mut cursor = epserde::new_aligned_cursor();
+    data.serialize_with(&SerializeOptions::new().min_align(64), &mut cursor)?;
+    let backend = cursor.into_inner();
+
+    let options = DeserializeOptions::new().min_align(64);
+    let eps = <Vec<u16>>::deserialize_eps_with(&options, &backend)?;
+    assert_eq!(eps, data.as_slice());
+    assert_eq!(eps.as_ptr() as usize % 64, 0);
+    let full = <Vec<u16>>::deserialize_full_with(&options, &mut std::io::Cursor::new(&backend))?;
+    assert_eq!(full, data);
+
+    // The minimum alignment is read from the header
+    let eps = <Vec<u16>>::deserialize_eps(&backend)?;
+    assert_eq!(eps, data.as_slice());
+    assert_eq!(eps.as_ptr() as usize % 64, 0);
+    let full = <Vec<u16>>::deserialize_full(&mut std::io::Cursor::new(&backend))?;
+    assert_eq!(full, data);
+
+    // An invalid minimum alignment in the header is detected
+    let mut backend = backend;
+    let pos = 8 + 2 + 2 + 1;
+    backend[pos..pos + size_of::<usize>()].copy_from_slice(&3_usize.to_ne_bytes());
+    assert!(matches!(
+        <Vec<u16>>::deserialize_eps(&backend),
+        Err(deser::Error::InvalidValue("MIN_ALIGN"))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_min_align_file() -> Result<()> {
+    let data = vec![1_u16, 2, 3];
+    let mut file = std::env::temp_dir();
+    file.push(format!("test_min_align_file_{}.bin", std::process::id()));
+    data.serialize_with(
+        &SerializeOptions::new().min_align(4096),
+        &mut std::io::BufWriter::new(std::fs::File::create(&file)?),
+    )?;
+
+    let mmap = <Vec<u16>>::mmap(&file, Flags::empty())?;
+    assert_eq!(*mmap, data.as_slice());
+    assert_eq!(mmap.as_ptr() as usize % 4096, 0);
+    let mem = <Vec<u16>>::load_mmap(&file, Flags::empty())?;
+    assert_eq!(*mem, data.as_slice());
+    let mem = <Vec<u16>>::load_mem(&file)?;
+    assert_eq!(*mem, data.as_slice());
+    assert_eq!(mem.as_ptr() as usize % 4096, 0);
+    assert_eq!(<Vec<u16>>::load_full(&file)?, data);
+    std::fs::remove_file(&file)?;
+    Ok(())
+}
+
+#[test]
+fn test_checksum() -> Result<()> {
+    let data = Data {
+        a: vec![1_u32, 2, 3],
+        b: 4,
+    };
+    let mut cursor = epserde::new_aligned_cursor();
+    let len = data.serialize_with(&SerializeOptions::new().checksum(true), &mut cursor)?;
+    let mut backend = cursor.into_inner();
+    assert_eq!(backend.len(), len);
+
+    let options = DeserializeOptions::new().checksum(true);
+    let full =
+        <Data<Vec<u32>>>::deserialize_full_with(&options, &mut std::io::Cursor::new(&backend))?;
+    assert_eq!(full, data);
+    let eps = <Data<Vec<u32>>>::deserialize_eps_with(&options, &backend)?;
+    assert_eq!(eps.a, data.a.as_slice());
+    let eps = <Data<Vec<u32>>>::deserialize_eps_mut_with(&options, &mut backend)?;
+    eps.a[0] = 0;
+
+    // We changed the data
+    assert!(matches!(
+        <Data<Vec<u32>>>::deserialize_eps_with(&options, &backend),
+        Err(deser::Error::ChecksumMismatch { .. })
+    ));
+    assert!(matches!(
+        <Data<Vec<u32>>>::deserialize_full_with(&options, &mut std::io::Cursor::new(&backend)),
+        Err(deser::Error::ChecksumMismatch { .. })
+    ));
+    assert!(matches!(
+        <Data<Vec<u32>>>::deserialize_eps_mut_with(&options, &mut backend),
+        Err(deser::Error::ChecksumMismatch { .. })
+    ));
+    // The checksum is not verified by default
+    assert_eq!(<Data<Vec<u32>>>::deserialize_eps(&backend)?.a[0], 0);
+    Ok(())
+}
+
+#[test]
+fn test_validation() -> Result<()> {
+    let data = vec![1_u32, 2, 3];
+    let mut cursor = epserde::new_aligned_cursor();
+    data.serialize(&mut cursor)?;
+    let backend = cursor.into_inner();
+
+    assert!(matches!(
+        <Vec<i32>>::deserialize_eps(&backend),
+        Err(deser::Error::WrongTypeHash { .. })
+    ));
+    let options = DeserializeOptions::new().validation(Validation::Basic);
+    assert_eq!(
+        <Vec<i32>>::deserialize_eps_with(&options, &backend)?,
+        [1, 2, 3]
+    );
+    Ok(())
+}
+
+// Parameters make the compile-time lint bail out
+#[derive(Epserde, Debug, PartialEq, Clone, Copy)]
+struct Point<T> {
+    x: T,
+    y: T,
+}
+
+#[test]
+fn test_mismatch_policy() -> Result<()> {
+    let data = vec![Point { x: 0_u32, y: 1 }; 10];
+    let mut cursor = epserde::new_aligned_cursor();
+    assert!(matches!(
+        data.serialize_with(
+            &SerializeOptions::new().mismatch_policy(MismatchPolicy::Strict),
+            &mut cursor,
+        ),
+        Err(ser::Error::ZeroCopyMismatch(_))
+    ));
+    let mut cursor = epserde::new_aligned_cursor();
+    data.serialize_with(
+        &SerializeOptions::new().mismatch_policy(MismatchPolicy::Ignore),
+        &mut cursor,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+struct Point {
+    x: usize,
+    y: usize,
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct Index<A, B> {
+    data: A,
+    origin: B,
+}
+
+#[test]
+fn test_box() {
+    let a = Box::new(vec![1_usize, 2, 3]);
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Box<Vec<usize>>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps: &[usize] = <Box<Vec<usize>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(a.as_slice(), eps);
+
+    // Boxes are transparent
+    let full = <Vec<usize>>::deserialize_full(&mut std::io::Cursor::new(&bytes)).unwrap();
+    assert_eq!(*a, full);
+
+    let a = Box::new(Point { x: 1, y: 2 });
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps: &Point = <Box<Point>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(*a, *eps);
+}
+
+#[test]
+fn test_rc_arc() {
+    type Shared = Index<Arc<Vec<Point>>, Rc<Point>>;
+    let data = Arc::new(vec![Point { x: 0, y: 1 }, Point { x: 2, y: 3 }]);
+    let a: Shared = Index {
+        data: data.clone(),
+        origin: Rc::new(Point { x: 4, y: 5 }),
+    };
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = Shared::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = Shared::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.data, data.as_slice());
+    assert_eq!(*eps.origin, Point { x: 4, y: 5 });
+}
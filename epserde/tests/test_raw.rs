@@ -0,0 +1,47 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use anyhow::Result;
+use epserde::prelude::*;
+use std::io::Write;
+
+#[test]
+fn test_raw() -> Result<()> {
+    let values = vec![vec![1_u64], vec![2, 3], vec![], vec![4, 5, 6]];
+
+    // A container with a three-byte header, followed by raw values
+    let mut cursor = epserde::new_aligned_cursor();
+    cursor.write_all(b"REC")?;
+    let mut offsets = vec![];
+    for value in &values {
+        let offset = cursor.position() as usize;
+        offsets.push(offset);
+        let written = value.serialize_raw(&mut cursor, offset)?;
+        assert_eq!(cursor.position() as usize, offset + written);
+
+        let mut with_header = epserde::new_aligned_cursor();
+        assert!(value.serialize(&mut with_header)? > written);
+    }
+    let backend = cursor.into_inner();
+
+    for (value, &offset) in values.iter().zip(&offsets) {
+        let eps = <Vec<u64>>::deserialize_eps_raw(&backend[offset..], offset)?;
+        assert_eq!(eps, value.as_slice());
+        // Zero-copy data is aligned with respect to the container
+        assert_eq!((eps.as_ptr() as usize - backend.as_ptr() as usize) % 8, 0);
+        let full = <Vec<u64>>::deserialize_full_raw(&mut &backend[offset..], offset)?;
+        assert_eq!(&full, value);
+    }
+
+    // A wrong base offset breaks alignment
+    assert!(matches!(
+        <Vec<u64>>::deserialize_eps_raw(&backend[offsets[1]..], offsets[1] + 1),
+        Err(deser::Error::AlignmentError)
+    ));
+    Ok(())
+}
@@ -10,6 +10,7 @@ use anyhow::Result;
 use epserde::prelude::*;
 
 #[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+#[allow(dead_code)]
 struct Data<A: PartialEq = usize, const Q: usize = 3> {
     a: A,
     b: [i32; Q],
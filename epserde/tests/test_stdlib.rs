@@ -0,0 +1,157 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use core::cmp::{Ordering, Reverse};
+use core::num::{NonZeroU32, NonZeroU64, Wrapping};
+use core::ops::{Range, RangeInclusive};
+use core::time::Duration;
+use epserde::prelude::*;
+
+macro_rules! impl_test {
+    ($ty:ty, $val:expr) => {{
+        let a: $ty = $val;
+        let mut buf = epserde::new_aligned_cursor();
+        a.serialize(&mut buf).unwrap();
+
+        buf.set_position(0);
+        let full = <$ty>::deserialize_full(&mut buf).unwrap();
+        assert_eq!(a, full);
+
+        let bytes = buf.into_inner();
+        let eps = <$ty>::deserialize_eps(&bytes).unwrap();
+        assert_eq!(a, eps);
+    }};
+}
+
+#[test]
+fn test_values() {
+    impl_test!(NonZeroU32, NonZeroU32::new(7).unwrap());
+    impl_test!(NonZeroU64, NonZeroU64::MAX);
+    impl_test!(Wrapping<u16>, Wrapping(42));
+    impl_test!(Reverse<i64>, Reverse(-3));
+    impl_test!(Ordering, Ordering::Less);
+    impl_test!(Ordering, Ordering::Equal);
+    impl_test!(Ordering, Ordering::Greater);
+    impl_test!(Duration, Duration::new(12, 345_678_901));
+    impl_test!(Range<usize>, 3..10);
+    impl_test!(RangeInclusive<i32>, -5..=5);
+}
+
+#[test]
+fn test_slices() {
+    let v = vec![
+        NonZeroU64::new(1).unwrap(),
+        NonZeroU64::new(u64::MAX).unwrap(),
+    ];
+    let mut buf = epserde::new_aligned_cursor();
+    v.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    assert_eq!(v, <Vec<NonZeroU64>>::deserialize_full(&mut buf).unwrap());
+    let bytes = buf.into_inner();
+    let eps: &[NonZeroU64] = <Vec<NonZeroU64>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(v, eps);
+
+    let v = vec![Reverse(Wrapping(1_u32)), Reverse(Wrapping(2))];
+    let mut buf = epserde::new_aligned_cursor();
+    v.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps: &[Reverse<Wrapping<u32>>] =
+        <Vec<Reverse<Wrapping<u32>>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(v, eps);
+
+    let v = vec![Duration::from_millis(1), Duration::from_secs(2)];
+    impl_test!(Vec<Duration>, v.clone());
+}
+
+#[test]
+fn test_invalid_nonzero() {
+    // Serialize a valid value and overwrite it with zero
+    let mut buf = epserde::new_aligned_cursor();
+    NonZeroU64::MIN.serialize(&mut buf).unwrap();
+    let mut bytes = buf.into_inner();
+    let len = bytes.len();
+    bytes[len - 8..].fill(0);
+
+    assert!(matches!(
+        NonZeroU64::deserialize_eps(&bytes),
+        Err(deser::Error::InvalidValue(_))
+    ));
+    assert!(matches!(
+        NonZeroU64::deserialize_full(&mut std::io::Cursor::new(&bytes)),
+        Err(deser::Error::InvalidValue(_))
+    ));
+
+    let v = vec![NonZeroU64::MIN; 4];
+    let mut buf = epserde::new_aligned_cursor();
+    v.serialize(&mut buf).unwrap();
+    let mut bytes = buf.into_inner();
+    let len = bytes.len();
+    bytes[len - 8..].fill(0);
+    assert!(matches!(
+        <Vec<NonZeroU64>>::deserialize_eps(&bytes),
+        Err(deser::Error::InvalidValue(_))
+    ));
+    assert!(matches!(
+        <Vec<NonZeroU64>>::deserialize_full(&mut std::io::Cursor::new(&bytes)),
+        Err(deser::Error::InvalidValue(_))
+    ));
+}
+
+#[test]
+fn test_invalid_zero_copy_struct() {
+    #[derive(Epserde, Debug, PartialEq, Clone, Copy)]
+    #[repr(C)]
+    #[zero_copy]
+    struct Data {
+        a: u32,
+        b: NonZeroU32,
+        c: Ordering,
+    }
+
+    let v = vec![
+        Data {
+            a: 0,
+            b: NonZeroU32::MIN,
+            c: Ordering::Less,
+        };
+        2
+    ];
+    let mut buf = epserde::new_aligned_cursor();
+    v.serialize(&mut buf).unwrap();
+    let mut bytes = buf.into_inner();
+    assert_eq!(v, <Vec<Data>>::deserialize_eps(&bytes).unwrap());
+
+    // Invalidate the ordering of the last element
+    let len = bytes.len();
+    bytes[len - 4] = 2;
+    assert!(matches!(
+        <Vec<Data>>::deserialize_eps(&bytes),
+        Err(deser::Error::InvalidValue("Ordering"))
+    ));
+
+    // Invalidate the non-zero field of the last element
+    bytes[len - 4] = 0;
+    bytes[len - 8..len - 4].fill(0);
+    assert!(matches!(
+        <Vec<Data>>::deserialize_eps(&bytes),
+        Err(deser::Error::InvalidValue("NonZeroU32"))
+    ));
+}
+
+#[test]
+fn test_invalid_duration() {
+    let mut buf = epserde::new_aligned_cursor();
+    Duration::new(1, 0).serialize(&mut buf).unwrap();
+    let mut bytes = buf.into_inner();
+    let len = bytes.len();
+    bytes[len - 4..].copy_from_slice(&1_000_000_000_u32.to_ne_bytes());
+    assert!(matches!(
+        Duration::deserialize_eps(&bytes),
+        Err(deser::Error::InvalidValue("Duration"))
+    ));
+}
@@ -0,0 +1,199 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::deser::{StrPool, StrPoolMut};
+use epserde::prelude::*;
+
+#[test]
+fn test_vec_string() {
+    let a = vec![
+        "a".to_string(),
+        String::new(),
+        "ε-serde".to_string(),
+        "xyz".repeat(1000),
+    ];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Vec<String>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+
+    let bytes = buf.into_inner();
+    let eps: StrPool = <Vec<String>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.len(), a.len());
+    assert!(!eps.is_empty());
+    for (i, s) in a.iter().enumerate() {
+        assert_eq!(eps.get(i), s);
+        assert_eq!(&eps[i], s);
+    }
+    assert_eq!(eps.iter().len(), a.len());
+    assert!(eps.iter().eq(a.iter().map(String::as_str)));
+    assert!(eps.iter().rev().eq(a.iter().rev().map(String::as_str)));
+    assert_eq!(a, eps);
+}
+
+#[test]
+fn test_box_slice_box_str() {
+    // Enough strings to write offsets in more than one chunk
+    let a: Box<[Box<str>]> = (0..3000).map(|i| i.to_string().into_boxed_str()).collect();
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Box<[Box<str>]>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+
+    let bytes = buf.into_inner();
+    let eps = <Box<[Box<str>]>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.len(), 3000);
+    assert_eq!(eps.get(2999), "2999");
+    assert_eq!(a, eps);
+}
+
+#[test]
+fn test_empty() {
+    let a: Vec<String> = vec![];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Vec<String>>::deserialize_full(&mut buf).unwrap();
+    assert!(full.is_empty());
+    let bytes = buf.into_inner();
+    let eps = <Vec<String>>::deserialize_eps(&bytes).unwrap();
+    assert!(eps.is_empty());
+    assert_eq!(eps.iter().next(), None);
+}
+
+#[test]
+fn test_str_pool_mut() {
+    let a = vec!["abc".to_string(), "def".to_string()];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    let mut bytes = buf.into_inner();
+    let mut eps: StrPoolMut = <Vec<String>>::deserialize_eps_mut(&mut bytes).unwrap();
+    eps.get_mut(1).make_ascii_uppercase();
+    assert_eq!(eps.get(1), "DEF");
+
+    let eps = <Vec<String>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, vec!["abc", "DEF"]);
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone)]
+struct Dictionary<A> {
+    words: A,
+}
+
+#[test]
+fn test_struct_str_pool() {
+    let a = Dictionary {
+        words: vec!["foo".to_string(), "bar".to_string()],
+    };
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let eps = <Dictionary<Vec<String>>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.words.get(0), "foo");
+    assert_eq!(eps.words.get(1), "bar");
+}
+
+/// Serialize `a` and return the bytes, together with the position of the
+/// offsets and of the concatenation of the strings.
+fn serialize_pool(a: Vec<String>) -> (Vec<u8>, usize, usize) {
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    let bytes = buf.into_inner();
+    let mut offsets = vec![];
+    let mut pos = 0_usize;
+    for s in &a {
+        offsets.extend_from_slice(&pos.to_ne_bytes());
+        pos += s.len();
+    }
+    offsets.extend_from_slice(&pos.to_ne_bytes());
+    let concat = a.concat().into_bytes();
+    let find = |pattern: &[u8]| {
+        bytes
+            .windows(pattern.len())
+            .position(|w| w == pattern)
+            .unwrap()
+    };
+    let (offsets_pos, data_pos) = (find(&offsets), find(&concat));
+    (bytes, offsets_pos, data_pos)
+}
+
+/// Check that full deserialization fails, and that ε-copy deserialization
+/// succeeds, as it checks strings only when they are accessed, but the
+/// string of given index and the pool are detected as invalid.
+fn assert_invalid(bytes: &[u8], index: usize) {
+    assert!(matches!(
+        <Vec<String>>::deserialize_full(&mut std::io::Cursor::new(bytes)),
+        Err(deser::Error::InvalidValue("StrPool"))
+    ));
+    let eps = <Vec<String>>::deserialize_eps(bytes).unwrap();
+    assert!(matches!(
+        eps.try_get(index),
+        Err(deser::Error::InvalidValue("StrPool"))
+    ));
+    assert!(matches!(
+        eps.check(),
+        Err(deser::Error::InvalidValue("StrPool"))
+    ));
+    assert!(std::panic::catch_unwind(|| eps.get(index)).is_err());
+
+    let mut bytes = bytes.to_vec();
+    let mut eps = <Vec<String>>::deserialize_eps_mut(&mut bytes).unwrap();
+    assert!(matches!(
+        eps.try_get_mut(index),
+        Err(deser::Error::InvalidValue("StrPool"))
+    ));
+    assert!(matches!(
+        eps.check(),
+        Err(deser::Error::InvalidValue("StrPool"))
+    ));
+}
+
+#[test]
+fn test_invalid_utf8() {
+    let a = vec!["ab".to_string(), "é".to_string()];
+    let (mut bytes, _, data_pos) = serialize_pool(a);
+    bytes[data_pos + 2] = 0xFF;
+    assert_invalid(&bytes, 1);
+}
+
+#[test]
+fn test_invalid_char_boundary() {
+    let a = vec!["ab".to_string(), "é".to_string()];
+    let (mut bytes, offsets_pos, _) = serialize_pool(a);
+    let at = offsets_pos + size_of::<usize>();
+    bytes[at..at + size_of::<usize>()].copy_from_slice(&3_usize.to_ne_bytes());
+    assert_invalid(&bytes, 0);
+}
+
+#[test]
+fn test_invalid_offsets() {
+    let a = vec!["ab".to_string(), "cd".to_string()];
+    let (mut bytes, offsets_pos, _) = serialize_pool(a);
+    let at = offsets_pos + size_of::<usize>();
+    bytes[at..at + size_of::<usize>()].copy_from_slice(&5_usize.to_ne_bytes());
+    assert_invalid(&bytes, 0);
+}
+
+#[test]
+fn test_invalid_bounds() {
+    let a = vec!["ab".to_string(), "cd".to_string()];
+    let (mut bytes, offsets_pos, _) = serialize_pool(a);
+    let at = offsets_pos + 2 * size_of::<usize>();
+    bytes[at..at + size_of::<usize>()].copy_from_slice(&3_usize.to_ne_bytes());
+    // The last offset is checked at deserialization time
+    assert!(matches!(
+        <Vec<String>>::deserialize_eps(&bytes),
+        Err(deser::Error::InvalidValue("StrPool"))
+    ));
+    assert!(matches!(
+        <Vec<String>>::deserialize_eps_mut(&mut bytes),
+        Err(deser::Error::InvalidValue("StrPool"))
+    ));
+}
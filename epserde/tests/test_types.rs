@@ -153,6 +153,7 @@ fn test_vec_vec_array_array_usize() {
 #[test]
 fn test_struct_deep() {
     #[derive(Epserde, Copy, Clone, Debug, PartialEq)]
+    #[deep_copy]
     struct Struct {
         a: usize,
         b: usize,
@@ -199,6 +200,7 @@ fn test_struct_zero() {
 #[test]
 fn test_tuple_struct_deep() {
     #[derive(Epserde, Copy, Clone, Debug, PartialEq)]
+    #[deep_copy]
     struct Tuple(usize, usize, i32);
     let a = Tuple(0, 1, 2);
     let mut buf = epserde::new_aligned_cursor();
@@ -244,6 +246,37 @@ fn test_enum_deep() {
         C(u64, Vec<usize>),
         D { a: i32, b: V },
     }
+
+    for a in [
+        Data::A,
+        Data::B(3),
+        Data::C(4, vec![1, 2, 3]),
+        Data::D {
+            a: 1,
+            b: vec![4, 5],
+        },
+    ] {
+        let mut buf = epserde::new_aligned_cursor();
+        a.serialize(&mut buf).unwrap();
+        buf.set_position(0);
+        let full = <Data>::deserialize_full(&mut buf).unwrap();
+        assert_eq!(a, full);
+        let bytes = &buf.into_inner();
+        let eps = <Data>::deserialize_eps(bytes).unwrap();
+        match (&a, eps) {
+            (Data::A, Data::A) => {}
+            (Data::B(x), Data::B(y)) => assert_eq!(*x, y),
+            (Data::C(x, v), Data::C(y, w)) => {
+                assert_eq!(*x, y);
+                assert_eq!(v, &w);
+            }
+            (Data::D { a, b }, Data::D { a: c, b: d }) => {
+                assert_eq!(*a, c);
+                assert_eq!(b, d);
+            }
+            _ => panic!(),
+        }
+    }
 }
 
 #[test]
@@ -303,7 +336,7 @@ fn test_enum_zero() {
     assert_eq!(a, *eps);
 
     let mut buf = epserde::new_aligned_cursor();
-    let a = Vec::from_iter(iter::repeat(Data::A).take(10));
+    let a = Vec::from_iter(iter::repeat_n(Data::A, 10));
     a.serialize(&mut buf).unwrap();
     buf.set_position(0);
     let full = <Vec<Data>>::deserialize_full(&mut buf).unwrap();
@@ -313,3 +346,54 @@ fn test_enum_zero() {
     let eps = <Vec<Data>>::deserialize_eps(bytes).unwrap();
     assert_eq!(a, *eps);
 }
+
+#[test]
+fn test_tuple_deep() {
+    let a = (vec![1_u32, 2, 3], "abc".to_string());
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <(Vec<u32>, String)>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps: (&[u32], &str) = <(Vec<u32>, String)>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, (a.0.as_slice(), a.1.as_str()));
+
+    let a = (
+        7_usize,
+        vec![1.0_f64, 2.0].into_boxed_slice(),
+        (1_u8, 2_u16),
+    );
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <(usize, Box<[f64]>, (u8, u16))>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = <(usize, Box<[f64]>, (u8, u16))>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.0, 7);
+    assert_eq!(eps.1, &[1.0, 2.0]);
+    assert_eq!(eps.2, &(1, 2));
+
+    let mut bytes = bytes;
+    let eps = <(usize, Box<[f64]>, (u8, u16))>::deserialize_eps_mut(&mut bytes).unwrap();
+    eps.1[0] = 3.0;
+    let eps = <(usize, Box<[f64]>, (u8, u16))>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps.1, &[3.0, 2.0]);
+}
+
+#[test]
+fn test_vec_tuple_deep() {
+    let a = vec![
+        ("a".to_string(), vec![1_usize, 2]),
+        ("b".to_string(), vec![3]),
+    ];
+    let mut buf = epserde::new_aligned_cursor();
+    a.serialize(&mut buf).unwrap();
+    buf.set_position(0);
+    let full = <Vec<(String, Vec<usize>)>>::deserialize_full(&mut buf).unwrap();
+    assert_eq!(a, full);
+    let bytes = buf.into_inner();
+    let eps = <Vec<(String, Vec<usize>)>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps, vec![("a", &[1, 2][..]), ("b", &[3][..])]);
+}
@@ -0,0 +1,90 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use epserde::prelude::*;
+use std::hash::Hasher;
+use xxhash_rust::xxh3::Xxh3;
+
+#[derive(Epserde, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+union Number {
+    int: u64,
+    float: f64,
+    bytes: [u8; 4],
+}
+
+#[derive(Epserde, Clone, Copy)]
+#[repr(C)]
+#[zero_copy]
+struct Tagged {
+    tag: u8,
+    value: Number,
+}
+
+#[test]
+fn test_union() {
+    let n = Number { float: 1.5 };
+    let mut cursor = epserde::new_aligned_cursor();
+    n.serialize(&mut cursor).unwrap();
+    cursor.set_position(0);
+    let full = Number::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(unsafe { full.float }, 1.5);
+    let bytes = cursor.into_inner();
+    let eps = Number::deserialize_eps(&bytes).unwrap();
+    assert_eq!(unsafe { eps.int }, 1.5_f64.to_bits());
+
+    let v = vec![
+        Tagged {
+            tag: 0,
+            value: Number { int: 42 },
+        },
+        Tagged {
+            tag: 1,
+            value: Number { float: -0.5 },
+        },
+    ];
+    let mut cursor = epserde::new_aligned_cursor();
+    v.serialize(&mut cursor).unwrap();
+    let mut bytes = cursor.into_inner();
+    let eps = <Vec<Tagged>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(eps[0].tag, 0);
+    assert_eq!(unsafe { eps[0].value.int }, 42);
+    assert_eq!(unsafe { eps[1].value.float }, -0.5);
+    let eps_mut = <Vec<Tagged>>::deserialize_eps_mut(&mut bytes).unwrap();
+    eps_mut[0].value.bytes = [1, 0, 0, 0];
+    let eps = <Vec<Tagged>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(unsafe { eps[0].value.int }, 1);
+}
+
+mod as_struct {
+    use epserde::TypeInfo;
+
+    #[derive(TypeInfo, Clone, Copy)]
+    #[repr(C)]
+    #[zero_copy]
+    pub struct Number {
+        int: u64,
+        float: f64,
+        bytes: [u8; 4],
+    }
+}
+
+fn type_hash<T: TypeHash>() -> u64 {
+    let mut hasher = Xxh3::with_seed(0);
+    T::type_hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_union_type_info() {
+    assert_eq!(Number::max_size_of(), 8);
+    assert_eq!(Tagged::max_size_of(), 8);
+    // A structure with the same fields has a different type hash
+    assert_ne!(type_hash::<Number>(), type_hash::<as_struct::Number>());
+}
@@ -64,3 +64,59 @@ test_zero!(
     [((i64, i32), i32); 2],
     [((-1_i64, 1), -1), ((-2_i64, 2), -2)]
 );
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(C, align(16))]
+#[zero_copy]
+struct Aligned {
+    a: u32,
+    b: u8,
+}
+
+#[derive(Epserde, Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(transparent)]
+#[zero_copy]
+struct Transparent(u64);
+
+test_zero!(test_repr_align, Aligned, Aligned { a: 1, b: 2 });
+test_zero!(test_repr_transparent, Transparent, Transparent(3));
+test_zero!(
+    test_array_repr_align,
+    [Aligned; 2],
+    [Aligned { a: 1, b: 2 }, Aligned { a: 3, b: 4 }]
+);
+
+#[derive(Epserde, Debug, PartialEq, Clone, Copy)]
+#[repr(transparent)]
+#[zero_copy]
+struct Meters {
+    value: f64,
+}
+
+#[test]
+fn test_transparent_zero_copy() {
+    const { assert!(<Meters as SerializeInner>::IS_ZERO_COPY) };
+    assert_eq!(core::mem::size_of::<Meters>(), core::mem::size_of::<f64>());
+
+    let data = Meters { value: 1.5 };
+    let mut cursor = epserde::new_aligned_cursor();
+    data.serialize(&mut cursor).unwrap();
+    cursor.set_position(0);
+    let full = Meters::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(data, full);
+    let bytes = cursor.into_inner();
+    let eps: &Meters = Meters::deserialize_eps(&bytes).unwrap();
+    assert_eq!(data, *eps);
+
+    // Vectors of transparent zero-copy structures are ε-copy deserialized
+    // as slices
+    let data = vec![data; 10];
+    let mut cursor = epserde::new_aligned_cursor();
+    data.serialize(&mut cursor).unwrap();
+    cursor.set_position(0);
+    let full = <Vec<Meters>>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(data, full);
+    let bytes = cursor.into_inner();
+    let eps: &[Meters] = <Vec<Meters>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(data, eps);
+}
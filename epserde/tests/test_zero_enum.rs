@@ -0,0 +1,193 @@
+/*
+ * SPDX-FileCopyrightText: 2023 Inria
+ *
+ * SPDX-License-Identifier: Apache-2.0 OR LGPL-2.1-or-later
+ */
+
+#![cfg(test)]
+
+use core::hash::Hasher;
+use epserde::prelude::*;
+use xxhash_rust::xxh3::Xxh3;
+
+#[derive(Epserde, Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+#[zero_copy]
+enum Color {
+    Red,
+    Green = 10,
+    Blue,
+}
+
+#[derive(Epserde, Clone, Copy, Debug, PartialEq)]
+#[repr(u16)]
+#[zero_copy]
+enum Shape {
+    Point,
+    Circle { radius: f32 } = 7,
+    Segment(u8, u64),
+    Flag(bool),
+}
+
+#[derive(Epserde, Clone, Copy, Debug, PartialEq)]
+#[repr(C, u8)]
+#[zero_copy]
+enum Value {
+    Int(i64),
+    Flag(bool),
+    Pair(u8, u32),
+}
+
+#[test]
+fn test_fieldless() {
+    assert_eq!(core::mem::size_of::<Color>(), 1);
+    let v = vec![Color::Red, Color::Blue, Color::Green, Color::Blue];
+    let mut cursor = epserde::new_aligned_cursor();
+    v.serialize(&mut cursor).unwrap();
+    cursor.set_position(0);
+    let full = <Vec<Color>>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(v, full);
+    let mut bytes = cursor.into_inner();
+    let eps = <Vec<Color>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(v.as_slice(), eps);
+
+    // Discriminants are 0, 10, and 11
+    let len = bytes.len();
+    assert_eq!(bytes[len - 4..], [0, 11, 10, 11]);
+    bytes[len - 1] = 12;
+    assert!(matches!(
+        <Vec<Color>>::deserialize_eps(&bytes),
+        Err(epserde::deser::Error::InvalidTag(12))
+    ));
+    let mut cursor = std::io::Cursor::new(&bytes);
+    assert!(<Vec<Color>>::deserialize_full(&mut cursor).is_err());
+}
+
+#[test]
+fn test_primitive_repr() {
+    let v = vec![
+        Shape::Point,
+        Shape::Circle { radius: 1.5 },
+        Shape::Segment(3, 1 << 40),
+        Shape::Flag(true),
+    ];
+    let mut cursor = epserde::new_aligned_cursor();
+    v.serialize(&mut cursor).unwrap();
+    cursor.set_position(0);
+    let full = <Vec<Shape>>::deserialize_full(&mut cursor).unwrap();
+    assert_eq!(v, full);
+    let mut bytes = cursor.into_inner();
+    let eps = <Vec<Shape>>::deserialize_eps(&bytes).unwrap();
+    assert_eq!(v.as_slice(), eps);
+
+    // The last element is Flag(true): its discriminant is 9, and the flag
+    // immediately follows the discriminant.
+    let start = bytes.len() - core::mem::size_of::<Shape>();
+    assert_eq!(u16::from_ne_bytes([bytes[start], bytes[start + 1]]), 9);
+    assert_eq!(bytes[start + 2], 1);
+    bytes[start + 2] = 2;
+    assert!(matches!(
+        <Vec<Shape>>::deserialize_eps(&bytes),
+        Err(epserde::deser::Error::InvalidValue("bool"))
+    ));
+    bytes[start + 2] = 1;
+    bytes[start..start + 2].copy_from_slice(&1_u16.to_ne_bytes());
+    assert!(matches!(
+        <Vec<Shape>>::deserialize_eps(&bytes),
+        Err(epserde::deser::Error::InvalidTag(1))
+    ));
+}
+
+#[test]
+fn test_c_repr() {
+    for a in [Value::Int(-1), Value::Flag(false), Value::Pair(1, 2)] {
+        let mut cursor = epserde::new_aligned_cursor();
+        a.serialize(&mut cursor).unwrap();
+        cursor.set_position(0);
+        let full = Value::deserialize_full(&mut cursor).unwrap();
+        assert_eq!(a, full);
+        let mut bytes = cursor.into_inner();
+        let eps = Value::deserialize_eps(&bytes).unwrap();
+        assert_eq!(a, *eps);
+        let eps_mut = Value::deserialize_eps_mut(&mut bytes).unwrap();
+        *eps_mut = Value::Flag(true);
+        assert_eq!(*Value::deserialize_eps(&bytes).unwrap(), Value::Flag(true));
+    }
+
+    // With repr(C), the payload is aligned to the largest alignment
+    // of the fields of all variants.
+    let a = Value::Flag(true);
+    let mut cursor = epserde::new_aligned_cursor();
+    a.serialize(&mut cursor).unwrap();
+    let mut bytes = cursor.into_inner();
+    let start = bytes.len() - core::mem::size_of::<Value>();
+    assert_eq!(bytes[start], 1);
+    assert_eq!(bytes[start + 8], 1);
+    bytes[start + 8] = 3;
+    assert!(Value::deserialize_eps(&bytes).is_err());
+    bytes[start + 8] = 1;
+    bytes[start] = 3;
+    assert!(matches!(
+        Value::deserialize_eps(&bytes),
+        Err(epserde::deser::Error::InvalidTag(3))
+    ));
+}
+
+mod original {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Clone, Copy)]
+    #[allow(dead_code)]
+    #[repr(u8)]
+    #[zero_copy]
+    pub enum Bit {
+        Zero = 0,
+        One = 1,
+    }
+}
+
+mod swapped {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Clone, Copy)]
+    #[allow(dead_code)]
+    #[repr(u8)]
+    #[zero_copy]
+    pub enum Bit {
+        Zero = 1,
+        One = 0,
+    }
+}
+
+mod wide {
+    use epserde::prelude::*;
+
+    #[derive(Epserde, Clone, Copy)]
+    #[allow(dead_code)]
+    #[repr(u16)]
+    #[zero_copy]
+    pub enum Bit {
+        Zero = 0,
+        One = 1,
+    }
+}
+
+fn type_hash<T: TypeHash>() -> u64 {
+    let mut hasher = Xxh3::with_seed(0);
+    T::type_hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_type_hash() {
+    // Discriminants and the integer representation are part of the type hash
+    assert_ne!(type_hash::<original::Bit>(), type_hash::<swapped::Bit>());
+    assert_ne!(type_hash::<original::Bit>(), type_hash::<wide::Bit>());
+
+    let mut cursor = epserde::new_aligned_cursor();
+    original::Bit::One.serialize(&mut cursor).unwrap();
+    assert!(matches!(
+        swapped::Bit::deserialize_eps(cursor.get_ref()),
+        Err(epserde::deser::Error::WrongTypeHash { .. })
+    ));
+}